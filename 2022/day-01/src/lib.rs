@@ -1,53 +1,11 @@
 use std::cmp::Reverse;
-use std::path::PathBuf;
 
-use clap::Parser;
+use common::error::SolveError;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 use sorted_vec::ReverseSortedVec;
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
-pub fn solve_part1(input: &str) -> String {
-    let result = (input.to_owned() + "\n").split('\n').fold((0, 0), |acc, it| {
-        if it.len() == 0 {
-            return if acc.1 < acc.0 { (0, acc.0) } else { (0, acc.1) };
-        }
-        return (acc.0 + it.parse::<i32>().unwrap(), acc.1);
-    }).1;
-    return result.to_string();
-}
-
-pub fn solve_part2(input: &str) -> String {
-    let mut sorted_vec: ReverseSortedVec<i32> = ReverseSortedVec::new();
-    let mut calories: i32 = 0;
-
-    (input.to_owned() + "\n").split('\n').for_each(|it| {
-        if it.len() == 0 {
-            sorted_vec.push(Reverse(calories));
-            calories = 0;
-        } else {
-            calories = calories + it.parse::<i32>().unwrap();
-        }
-    });
-
-    let result: i32 = sorted_vec.iter()
-        .take(3)
-        .fold(0, |acc, it| acc + it.0);
-
-    return result.to_string();
-}
-
-#[cfg(test)]
-mod tests {
-    use std::assert_eq;
-
-    use super::*;
-
-    const INPUT: &str = "1000
+const INPUT: &str = "1000
 2000
 3000
 
@@ -62,15 +20,63 @@ mod tests {
 
 10000";
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<Vec<i32>>;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.blocks(input).into_iter()
+            .map(|elf| elf.lines().map(|it| parser.value::<i32>(it)).collect())
+            .collect();
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: i32 = input.iter()
+            .map(|elf| elf.iter().sum())
+            .max()
+            .unwrap_or(0);
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let mut sorted_vec: ReverseSortedVec<i32> = ReverseSortedVec::new();
+        input.iter().for_each(|elf| {
+            sorted_vec.push(Reverse(elf.iter().sum()));
+        });
+
+        let result: i32 = sorted_vec.iter()
+            .take(3)
+            .fold(0, |acc, it| acc + it.0);
+
+        return Ok(Answer::from(result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_eq;
+
+    use super::*;
+
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day01::solve_part1(INPUT).unwrap();
         assert_eq!(result, "24000");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day01::solve_part2(INPUT).unwrap();
         assert_eq!(result, "45000");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day01>();
+    }
+}
@@ -1,36 +1,27 @@
-use std::path::PathBuf;
+use common::error::SolveError;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
-use clap::Parser;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
-enum RPS {
+pub enum RPS {
     Rock,
     Paper,
     Scissors
 }
 
-enum Result {
+pub enum Outcome {
     Win,
     Lose,
     Draw
 }
 
 impl RPS {
-    fn create(char: &str) -> Option<RPS> {
-        return match char {
-            "A" => Some(RPS::Rock),
-            "X" => Some(RPS::Rock),
-            "B" => Some(RPS::Paper),
-            "Y" => Some(RPS::Paper),
-            "C" => Some(RPS::Scissors),
-            "Z" => Some(RPS::Scissors),
-            _ => None
+    /// Shape written as one of `letters`, in the order rock, paper, scissors.
+    fn create<'a>(parser: &Parser<'a>, char: &'a str, letters: [&str; 3]) -> Result<RPS, ParseError> {
+        return match letters.iter().position(|it| *it == char) {
+            Some(0) => Ok(RPS::Rock),
+            Some(1) => Ok(RPS::Paper),
+            Some(2) => Ok(RPS::Scissors),
+            _ => Err(parser.error(char, format!("expected '{}', '{}' or '{}'", letters[0], letters[1], letters[2])))
         }
     }
 
@@ -60,86 +51,106 @@ impl RPS {
         }
     }
 
-    fn score_for_result(&self, result: &Result) -> u8 {
+    fn score_for_result(&self, result: &Outcome) -> u8 {
         return match self {
             RPS::Rock => {
                 match result {
-                    Result::Win => 8,
-                    Result::Lose => 3,
-                    Result::Draw => 4
+                    Outcome::Win => 8,
+                    Outcome::Lose => 3,
+                    Outcome::Draw => 4
                 }
             }
             RPS::Paper => {
                 match result {
-                    Result::Win => 9,
-                    Result::Lose => 1,
-                    Result::Draw => 5
+                    Outcome::Win => 9,
+                    Outcome::Lose => 1,
+                    Outcome::Draw => 5
                 }
             }
             RPS::Scissors => {
                 match result {
-                    Result::Win => 7,
-                    Result::Lose => 2,
-                    Result::Draw => 6
+                    Outcome::Win => 7,
+                    Outcome::Lose => 2,
+                    Outcome::Draw => 6
                 }
             }
         }
     }
 }
 
-impl Result {
-    fn create(char: &str) -> Option<Result> {
+impl Outcome {
+    fn create<'a>(parser: &Parser<'a>, char: &'a str) -> Result<Outcome, ParseError> {
         return match char {
-            "X" => Some(Result::Lose),
-            "Y" => Some(Result::Draw),
-            "Z" => Some(Result::Win),
-            _ => None
+            "X" => Ok(Outcome::Lose),
+            "Y" => Ok(Outcome::Draw),
+            "Z" => Ok(Outcome::Win),
+            _ => Err(parser.error(char, "expected 'X', 'Y' or 'Z'"))
         }
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let result: u32 = input.split("\n").fold(0, |acc, line| {
-        let game: Vec<&str> = line.split(" ").collect();
+const INPUT: &str = "A Y
+B X
+C Z";
 
-        let left: RPS = RPS::create(game.first().unwrap()).expect("Invalid input");
-        let right: RPS = RPS::create(game.last().unwrap()).expect("Invalid input");
+pub struct Day02;
 
-        return acc + right.score(&left) as u32;
-    });
-    return result.to_string();
-}
+impl Solution for Day02 {
+    /// Shape of the opponent, then the second column read as a shape (part 1) and as an outcome (part 2).
+    type Input = Vec<(RPS, RPS, Outcome)>;
+    type Params = ();
 
-pub fn solve_part2(input: &str) -> String {
-    let result: u32 = input.split("\n").fold(0, |acc, line| {
-        let game: Vec<&str> = line.split(" ").collect();
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
 
-        let left: RPS = RPS::create(game.first().unwrap()).expect("Invalid input");
-        let right: Result = Result::create(game.last().unwrap()).expect("Invalid input");
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.lines().map(|line| {
+            let [left, right] = parser.fields(line, " ")?;
+            let opponent: RPS = RPS::create(&parser, left, ["A", "B", "C"])?;
+            let shape: RPS = RPS::create(&parser, right, ["X", "Y", "Z"])?;
+            let outcome: Outcome = Outcome::create(&parser, right)?;
+            return Ok((opponent, shape, outcome));
+        }).collect();
+    }
 
-        return acc + left.score_for_result(&right) as u32;
-    });
-    return result.to_string();
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u32 = input.iter().fold(0, |acc, (left, right, _)| {
+            return acc + right.score(left) as u32;
+        });
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u32 = input.iter().fold(0, |acc, (left, _, right)| {
+            return acc + left.score_for_result(right) as u32;
+        });
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "A Y
-B X
-C Z";
-
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day02::solve_part1(INPUT).unwrap();
         assert_eq!(result, "15");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day02::solve_part2(INPUT).unwrap();
         assert_eq!(result, "12");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_invalid_shape() {
+        assert!(Day02::parse("A Y\nX B").is_err());
+    }
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day02>();
+    }
+}
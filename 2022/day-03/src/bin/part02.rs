@@ -1,9 +1,7 @@
-use std::fs;
-use clap::Parser;
-use aoc2022_day_03::{Cli, solve_part2};
+use std::process::ExitCode;
 
-fn main() {
-    let args: Cli = Cli::parse();
-    let input: String = fs::read_to_string(args.data_dir.join("input.txt")).unwrap();
-    println!("{}", solve_part2(&input));
+use aoc2022_day_03::Day03;
+
+fn main() -> ExitCode {
+    return common::cli::run::<Day03>(2022, 3, 2);
 }
\ No newline at end of file
@@ -1,57 +1,86 @@
 use std::collections::HashSet;
-use std::path::PathBuf;
 
-use clap::Parser;
+use common::error::SolveError;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
+/// Priority of an item type, `a`-`z` are 1-26 and `A`-`Z` are 27-52.
+fn priority(char: u8) -> u8 {
+    return if char >= b'a' { char - b'a' + 1 } else { char - b'A' + 27 };
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let result: u32 = input.split("\n").fold(0, |acc, line| {
-        let parts: (&str, &str) = line.split_at(line.len() / 2);
-        let set: HashSet<u8> = HashSet::from_iter(parts.1.bytes().into_iter());
-        let char: u8 = parts.0.bytes().find(|it| set.contains(it)).unwrap();
-        let score = if char >= 97 { char - 96 } else { char - 38 };
-        return acc + score as u32;
-    });
-    return result.to_string();
+/// Priority of the item shared by a group of items.
+fn shared(item: Option<&u8>) -> Result<u32, SolveError> {
+    let priority: &u8 = item.ok_or_else(|| SolveError::NoSolution("items share no item type".to_string()))?;
+    return Ok(*priority as u32);
 }
 
-pub fn solve_part2(input: &str) -> String {
-    let result: u32 = input.split("\n").collect::<Vec::<&str>>().chunks(3).into_iter().fold(0, |acc, chunk| {
-        let set1: HashSet<u8> = HashSet::from_iter(chunk[1].bytes().into_iter());
-        let set2: HashSet<u8> = HashSet::from_iter(chunk[2].bytes().into_iter());
-        let char: u8 = chunk[0].bytes().find(|it| set1.contains(it) && set2.contains(it)).unwrap();
-        let score = if char >= 97 { char - 96 } else { char - 38 };
-        return acc + score as u32;
-    });
-    return result.to_string();
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    /// Priorities of the items in each rucksack
+    type Input = Vec<Vec<u8>>;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.lines().map(|line| {
+            if let Some((idx, _)) = line.char_indices().find(|(_, it)| !it.is_ascii_alphabetic()) {
+                return Err(parser.error(&line[idx..], "expected an item letter"));
+            }
+            return Ok(line.bytes().map(priority).collect());
+        }).collect();
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u32 = input.iter().map(|line| {
+            let parts: (&[u8], &[u8]) = line.split_at(line.len() / 2);
+            let set: HashSet<&u8> = HashSet::from_iter(parts.1);
+            return shared(parts.0.iter().find(|it| set.contains(it)));
+        }).sum::<Result<u32, SolveError>>()?;
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        if input.len() % 3 != 0 {
+            return Err(SolveError::NoSolution(format!("{} rucksacks cannot be split into groups of three", input.len())));
+        }
+        let result: u32 = input.chunks(3).map(|chunk| {
+            let set1: HashSet<&u8> = HashSet::from_iter(&chunk[1]);
+            let set2: HashSet<&u8> = HashSet::from_iter(&chunk[2]);
+            return shared(chunk[0].iter().find(|it| set1.contains(it) && set2.contains(it)));
+        }).sum::<Result<u32, SolveError>>()?;
+        return Ok(Answer::from(result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day03::solve_part1(INPUT).unwrap();
         assert_eq!(result, "157");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day03::solve_part2(INPUT).unwrap();
         assert_eq!(result, "70");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day03>();
+    }
+}
@@ -1,9 +1,7 @@
-use std::fs;
-use clap::Parser;
-use aoc2022_day_04::{Cli, solve_part2};
+use std::process::ExitCode;
 
-fn main() {
-    let args: Cli = Cli::parse();
-    let input: String = fs::read_to_string(args.data_dir.join("input.txt")).unwrap();
-    println!("{}", solve_part2(&input));
+use aoc2022_day_04::Day04;
+
+fn main() -> ExitCode {
+    return common::cli::run::<Day04>(2022, 4, 2);
 }
\ No newline at end of file
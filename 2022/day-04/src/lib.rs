@@ -1,76 +1,65 @@
-use std::path::PathBuf;
+use common::error::SolveError;
+use common::interval::Interval;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
-use clap::Parser;
-use itertools::Itertools;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
-fn to_range(input: &str) -> (u32, u32) {
-    return input.split("-")
-        .map(|it| it.parse::<u32>().unwrap())
-        .next_tuple()
-        .unwrap();
+fn to_range<'a>(parser: &Parser<'a>, input: &'a str) -> Result<Interval<u32>, ParseError> {
+    let [first, last] = parser.fields(input, "-")?;
+    return Ok(Interval::closed(parser.value(first)?, parser.value(last)?));
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let result: u32 = input.split("\n").fold(0, |acc, chunk| {
-        let parts: Vec<&str> = chunk.split(",").collect();
-
-        let left: (u32, u32) = to_range(parts.first().unwrap());
-        let right: (u32, u32) = to_range(parts.last().unwrap());
+const INPUT: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
 
-        let range: (u32, u32) = (
-            if left.0 > right.0 { right.0 } else { left.0 },
-            if left.1 > right.1 { left.1 } else { right.1 }
-        );
+pub struct Day04;
 
-        return acc + if left == range || right == range { 1u32 } else { 0u32 };
-    });
-    return result.to_string();
-}
+impl Solution for Day04 {
+    type Input = Vec<(Interval<u32>, Interval<u32>)>;
+    type Params = ();
 
-pub fn solve_part2(input: &str) -> String {
-    let result: u32 = input.split("\n").fold(0, |acc, chunk| {
-        let parts: Vec<&str> = chunk.split(",").collect();
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
 
-        let left: (u32, u32) = to_range(parts.first().unwrap());
-        let right: (u32, u32) = to_range(parts.last().unwrap());
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.lines().map(|chunk| {
+            let [left, right] = parser.fields(chunk, ",")?;
+            return Ok((to_range(&parser, left)?, to_range(&parser, right)?));
+        }).collect();
+    }
 
-        let range: (u32, u32) = (
-            if left.0 > right.0 { left.0 } else { right.0 },
-            if left.1 > right.1 { right.1 } else { left.1 }
-        );
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: usize = input.iter().filter(|(left, right)| left.covers(right) || right.covers(left)).count();
+        return Ok(Answer::from(result));
+    }
 
-        return acc + if range.0 <= range.1 { 1 } else { 0 };
-    });
-    return result.to_string();
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: usize = input.iter().filter(|(left, right)| left.overlaps(right)).count();
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8";
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day04::solve_part1(INPUT).unwrap();
         assert_eq!(result, "2");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day04::solve_part2(INPUT).unwrap();
         assert_eq!(result, "4");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day04>();
+    }
+}
@@ -1,37 +1,30 @@
 extern crate core;
 
-use std::path::PathBuf;
-
-use clap::Parser;
+use common::error::SolveError;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 use itertools::Itertools;
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
 #[derive(Debug)]
-struct Ship {
+pub struct Ship {
     stacks: Vec<Vec<char>>,
     instructions: Vec<(u32, u32, u32)>,
 }
 
 impl Ship {
-    fn create(input: &str) -> Ship {
+    fn create(input: &str) -> Result<Ship, ParseError> {
+        let parser: Parser = Parser::create(input);
         let mut stacks: Vec<Vec<char>> = Vec::new();
         let mut instructions: Vec<(u32, u32, u32)> = Vec::new();
 
-        input.split("\n").for_each(|line| {
+        for line in parser.lines() {
             if line.starts_with("move") {
-                let (count, from, to) = line.strip_prefix("move ").unwrap()
-                    .split(" ")
-                    .enumerate()
-                    .filter_map(|it| if (it.0 + 1) % 2 == 1 { Some(it.1.parse::<u32>().unwrap()) } else { None })
-                    .collect_tuple()
-                    .unwrap();
-                instructions.push((count, from, to));
+                let [_, count, _, from, _, to] = parser.fields(line, " ")?;
+                let (from, to): (u32, u32) = (parser.value(from)?, parser.value(to)?);
+                if from == 0 || to == 0 || from as usize > stacks.len() || to as usize > stacks.len() {
+                    return Err(parser.error(line, format!("stacks are numbered from 1 to {}", stacks.len())));
+                }
+                instructions.push((parser.value(count)?, from, to));
             } else if !line.trim().starts_with('1') && !line.trim().is_empty() {
                 line.chars().chunks(4).into_iter()
                     .enumerate()
@@ -42,59 +35,85 @@ impl Ship {
                             stacks.push(Vec::new());
                         }
 
-                        match chars.iter().find(|ch| ch.is_alphabetic()) {
-                            Some(ch) => stacks[it.0].push(*ch),
-                            None => {}
+                        if let Some(ch) = chars.iter().find(|ch| ch.is_alphabetic()) {
+                            stacks[it.0].push(*ch);
                         }
                     })
             }
-        });
+        }
 
-        return Ship {
-            stacks: stacks.iter().map(|it| it.iter().rev().map(|it| *it).collect()).collect(),
+        return Ok(Ship {
+            stacks: stacks.iter().map(|it| it.iter().rev().copied().collect()).collect(),
             instructions,
-        };
+        });
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let mut ship: Ship = Ship::create(input);
+const INPUT: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
 
-    ship.instructions.iter_mut().for_each(|it| {
-        let (count, from_idx, to_idx) = *it;
-        let from: &mut Vec<char> = ship.stacks.get_mut((from_idx - 1) as usize).unwrap();
-        let crates: Vec<char> = (0..count).map(|_| from.pop().expect("No more crates left")).collect();
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
 
-        let to: &mut Vec<char> = ship.stacks.get_mut((to_idx - 1) as usize).unwrap();
-        crates.iter().for_each(|it| to.push(*it));
-    });
+pub struct Day05;
 
-    let result: Vec<char> = ship.stacks.iter()
-        .filter_map(|it| it.last())
-        .map(|it| *it)
-        .collect();
+impl Solution for Day05 {
+    type Input = Ship;
+    type Params = ();
 
-    return String::from_iter(result);
-}
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        return Ship::create(input);
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let mut stacks: Vec<Vec<char>> = input.stacks.clone();
+
+        for (count, from_idx, to_idx) in input.instructions.iter().copied() {
+            let from: &mut Vec<char> = stacks.get_mut((from_idx - 1) as usize).unwrap();
+            if from.len() < count as usize {
+                return Err(SolveError::NoSolution(format!("cannot move {} crates from stack {} with {}", count, from_idx, from.len())));
+            }
+            let crates: Vec<char> = (0..count).filter_map(|_| from.pop()).collect();
 
-pub fn solve_part2(input: &str) -> String {
-    let mut ship: Ship = Ship::create(input);
+            let to: &mut Vec<char> = stacks.get_mut((to_idx - 1) as usize).unwrap();
+            crates.iter().for_each(|it| to.push(*it));
+        }
 
-    ship.instructions.iter_mut().for_each(|it| {
-        let (count, from_idx, to_idx) = *it;
-        let from: &mut Vec<char> = ship.stacks.get_mut((from_idx - 1) as usize).unwrap();
-        let crates: Vec<char> = (0..count).map(|_| from.pop().expect("No more crates left")).rev().collect();
+        let result: Vec<char> = stacks.iter()
+            .filter_map(|it| it.last())
+            .copied()
+            .collect();
 
-        let to: &mut Vec<char> = ship.stacks.get_mut((to_idx - 1) as usize).unwrap();
-        crates.iter().rev().for_each(|it| to.push(*it));
-    });
+        return Ok(Answer::from(String::from_iter(result)));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let mut stacks: Vec<Vec<char>> = input.stacks.clone();
+
+        for (count, from_idx, to_idx) in input.instructions.iter().copied() {
+            let from: &mut Vec<char> = stacks.get_mut((from_idx - 1) as usize).unwrap();
+            if from.len() < count as usize {
+                return Err(SolveError::NoSolution(format!("cannot move {} crates from stack {} with {}", count, from_idx, from.len())));
+            }
+            let crates: Vec<char> = (0..count).filter_map(|_| from.pop()).rev().collect();
+
+            let to: &mut Vec<char> = stacks.get_mut((to_idx - 1) as usize).unwrap();
+            crates.iter().rev().for_each(|it| to.push(*it));
+        }
 
-    let result: Vec<char> = ship.stacks.iter()
-        .filter_map(|it| it.last())
-        .map(|it| *it)
-        .collect();
+        let result: Vec<char> = stacks.iter()
+            .filter_map(|it| it.last())
+            .copied()
+            .collect();
 
-    return String::from_iter(result);
+        return Ok(Answer::from(String::from_iter(result)));
+    }
 }
 
 #[cfg(test)]
@@ -103,25 +122,20 @@ mod tests {
 
     use super::*;
 
-    const INPUT: &str = "    [D]
-[N] [C]
-[Z] [M] [P]
- 1   2   3
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2";
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day05::solve_part1(INPUT).unwrap();
         assert_eq!(result, "CMZ");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day05::solve_part2(INPUT).unwrap();
         assert_eq!(result, "MCD");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day05>();
+    }
+}
@@ -1,9 +1,7 @@
-use std::fs;
-use clap::Parser;
-use aoc2023_day_01::{Cli, solve_part1};
+use std::process::ExitCode;
 
-fn main() {
-    let args: Cli = Cli::parse();
-    let input: String = fs::read_to_string(args.data_dir.join("input.txt")).unwrap();
-    println!("{}", solve_part1(&input));
+use aoc2023_day_01::Day01;
+
+fn main() -> ExitCode {
+    return common::cli::run::<Day01>(2023, 1, 1);
 }
\ No newline at end of file
@@ -1,108 +1,110 @@
-use regex::Regex;
-use std::path::PathBuf;
+use common::error::SolveError;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
-use clap::Parser;
+const INPUT1: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
+const INPUT2: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+7bbxlhgdbrh9sph44sbboneoneightxcn";
 
-pub fn solve_part1(input: &str) -> String {
-    let re: Regex = Regex::new(r"(?i)(\d{1})").unwrap();
-
-    let result: i64 = input.split("\n")
-        .map(|line| {
-            let numbers: Vec<i64> = re.find_iter(line)
-                .map(|it| it.as_str().parse().ok().unwrap())
-                .collect();
-            return match numbers.len() {
-                0 => 0,
-                1 => numbers[0] * 10 + numbers[0],
-                _n => numbers[0] * 10 + numbers.last().unwrap(),
-            };
-        }).sum();
-
-    return result.to_string();
+const DIGITS: [&str; 10] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// Digits of a calibration line in order of appearance.
+pub struct Calibration {
+    digits: Vec<u32>,
+    /// Digits including the spelled-out ones, which may overlap like in `twone`
+    spelled: Vec<u32>,
 }
 
-pub fn solve_part2(input: &str) -> String {
-    let digits: Vec<&str> = vec!["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
-    let rev_digits: Vec<String> = digits.iter().map(|&s| s.chars().rev().collect::<String>()).collect();
-
-    let mut pattern = r"(?i)(\d{1}|".to_owned();
-    pattern.push_str(&digits.join("|"));
-    pattern.push_str(")");
-
-    let mut rev_pattern = r"(?i)(\d{1}|".to_owned();
-    rev_pattern.push_str(&rev_digits.join("|"));
-    rev_pattern.push_str(")");
-
-    let re: Regex = Regex::new(pattern.as_str()).unwrap();
-    let rev_re: Regex = Regex::new(rev_pattern.as_str()).unwrap();
-
-    let result: i64 = input.split("\n")
-        .map(|line| {
-            let mut numbers: Vec<i64> = vec![0,2];
-
-            let first = re.find(line);
-            if !first.is_none() {
-                let group = first.unwrap().as_str();
-                numbers[0] = match group.parse::<i64>() {
-                    Ok(num) => num,
-                    Err(_) => digits.iter().position(|&digit| digit == group).unwrap() as i64
-                };
-            } else {
-                numbers[0] = 0;
+impl Calibration {
+    fn create(line: &str) -> Calibration {
+        let bytes: &[u8] = line.as_bytes();
+        let mut digits: Vec<u32> = Vec::new();
+        let mut spelled: Vec<u32> = Vec::new();
+
+        for pos in 0..bytes.len() {
+            if bytes[pos].is_ascii_digit() {
+                let digit: u32 = (bytes[pos] - b'0') as u32;
+                digits.push(digit);
+                spelled.push(digit);
+                continue;
             }
-
-            let rev_line: String = line.chars().rev().collect();
-            let last = rev_re.find(rev_line.as_str());
-            if !last.is_none() {
-                let group = last.unwrap().as_str();
-                numbers[1] = match group.parse::<i64>() {
-                    Ok(num) => num,
-                    Err(_) => rev_digits.iter().position(|digit| digit.as_str() == group).unwrap() as i64
-                };
-            } else {
-                numbers[1] = 0;
+            let word: Option<usize> = DIGITS.iter()
+                .position(|word| bytes[pos..].len() >= word.len() && bytes[pos..pos + word.len()].eq_ignore_ascii_case(word.as_bytes()));
+            if let Some(digit) = word {
+                spelled.push(digit as u32);
             }
+        }
+
+        return Calibration { digits, spelled };
+    }
+
+    /// Number made of the first and the last digit, 0 for a line without digits.
+    fn value(digits: &[u32]) -> u32 {
+        return match (digits.first(), digits.last()) {
+            (Some(first), Some(last)) => first * 10 + last,
+            _ => 0,
+        };
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<Calibration>;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT1, INPUT2];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return Ok(parser.lines().map(Calibration::create).collect());
+    }
 
-            return numbers[0] * 10 + numbers[1];
-        }).sum();
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u32 = input.iter().map(|it| Calibration::value(&it.digits)).sum();
+        return Ok(Answer::from(result));
+    }
 
-    return result.to_string();
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u32 = input.iter().map(|it| Calibration::value(&it.spelled)).sum();
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT1: &str = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet";
-
-    const INPUT2: &str = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen
-7bbxlhgdbrh9sph44sbboneoneightxcn";
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT1);
+        let result = Day01::solve_part1(INPUT1).unwrap();
         assert_eq!(result, "142");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT2);
+        let result = Day01::solve_part2(INPUT2).unwrap();
         assert_eq!(result, "359");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_mixed_case() {
+        let result = Day01::solve_part2("Two3\nfoo٣Nine").unwrap();
+        assert_eq!(result, "122");
+    }
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day01>();
+    }
+}
@@ -1,13 +1,6 @@
-use std::path::PathBuf;
-
-use clap::Parser;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
+use common::error::SolveError;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
 pub struct CubesLimits {
     pub red: u32,
@@ -22,11 +15,14 @@ struct GameRound {
     blue: u32,
 }
 
-impl CubesLimits {
+impl Default for CubesLimits {
+    /// Cubes loaded into the bag in the real puzzle.
     fn default() -> CubesLimits {
-        CubesLimits { red: 0, green: 0, blue: 0 }
+        return CubesLimits { red: 12, green: 13, blue: 14 };
     }
+}
 
+impl CubesLimits {
     fn power(&self) -> u32 {
         return self.red * self.green * self.blue;
     }
@@ -37,19 +33,19 @@ impl GameRound {
         GameRound { red: 0, green: 0, blue: 0 }
     }
 
-    fn new(round_input: &str) -> GameRound {
+    fn create<'a>(parser: &Parser<'a>, round_input: &'a str) -> Result<GameRound, ParseError> {
         let mut round: GameRound = GameRound::default();
-        let parts: Vec<&str> = round_input.split(",").collect();
-        for part in parts {
-            if part.ends_with(" red") {
-                round.red = round.red + part.strip_suffix(" red").unwrap().trim().parse::<u32>().unwrap();
-            } else if part.ends_with(" green") {
-                round.green = round.green + part.strip_suffix(" green").unwrap().trim().parse::<u32>().unwrap();
-            } else if part.ends_with(" blue") {
-                round.blue = round.blue + part.strip_suffix(" blue").unwrap().trim().parse::<u32>().unwrap();
+        for part in round_input.split(",") {
+            let (count, color) = parser.key_value(part.trim(), " ")?;
+            let count: u32 = parser.value(count)?;
+            match color {
+                "red" => round.red += count,
+                "green" => round.green += count,
+                "blue" => round.blue += count,
+                _ => return Err(parser.error(color, format!("unknown color '{}'", color))),
             }
         }
-        return round;
+        return Ok(round);
     }
 
     fn is_valid(&self, limits: &CubesLimits) -> bool {
@@ -58,18 +54,18 @@ impl GameRound {
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     index: u32,
     rounds: Vec<GameRound>,
 }
 
 impl Game {
-    fn new(game_input: &str) -> Game {
-        let parts: Vec<&str> = game_input.split(":").collect();
+    fn create<'a>(parser: &Parser<'a>, game_input: &'a str) -> Result<Game, ParseError> {
+        let (index, rounds) = parser.key_value(game_input, ":")?;
+        let index: u32 = parser.value(parser.strip_prefix(index, "Game ")?)?;
+        let rounds: Vec<GameRound> = rounds.split(";").map(|it| GameRound::create(parser, it)).collect::<Result<_, _>>()?;
 
-        let index = parts.first().unwrap().strip_prefix("Game ").unwrap();
-        let rounds: Vec<GameRound> = parts.last().unwrap().split(";").map(|it| GameRound::new(it)).collect();
-        return Game { index: index.parse().unwrap(), rounds };
+        return Ok(Game { index, rounds });
     }
 
     fn is_possible(&self, limits: &CubesLimits) -> bool {
@@ -77,7 +73,7 @@ impl Game {
     }
 
     fn get_min_limits(&self) -> CubesLimits {
-        let mut limit = CubesLimits::default();
+        let mut limit = CubesLimits { red: 0, green: 0, blue: 0 };
 
         self.rounds.iter().for_each(|it| {
             if limit.red < it.red {
@@ -97,45 +93,61 @@ impl Game {
     }
 }
 
-pub fn solve_part1(input: &str, limits: &CubesLimits) -> String {
-    let result: u32 = input.split("\n")
-        .map(|it| Game::new(it))
-        .map(|it| if it.is_possible(limits) { it.index } else { 0 })
-        .sum();
+const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+pub struct Day02;
 
-    return result.to_string();
-}
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+    type Params = CubesLimits;
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
 
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.lines().map(|it| Game::create(&parser, it)).collect();
+    }
 
-pub fn solve_part2(input: &str) -> String {
-    let result: u32 = input.split("\n")
-        .map(|it| Game::new(it))
-        .map(|it| it.get_min_limits())
-        .map(|it| it.power())
-        .sum();
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<Answer, SolveError> {
+        let result: u32 = input.iter()
+            .map(|it| if it.is_possible(params) { it.index } else { 0 })
+            .sum();
 
-    return result.to_string();
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Result<Answer, SolveError> {
+        let result: u32 = input.iter()
+            .map(|it| it.get_min_limits())
+            .map(|it| it.power())
+            .sum();
+
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT, &CubesLimits { red: 12, green: 13, blue: 14 });
+        let result = Day02::part1(&Day02::parse(INPUT).unwrap(), &CubesLimits { red: 12, green: 13, blue: 14 }).unwrap();
         assert_eq!(result, "8");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day02::solve_part2(INPUT).unwrap();
         assert_eq!(result, "2286");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day02>();
+    }
+}
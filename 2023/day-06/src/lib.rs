@@ -1,64 +1,89 @@
-use std::path::PathBuf;
-use clap::Parser;
-use itertools::Itertools;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
+use common::error::SolveError;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
-fn calculate_options_dummy(time: u32, duration: u32) -> u32 {
-    let mut options_count: u32 = 0;
-    for idx in (1..time-1).rev() {
-        let speed: u32 = time - idx;
-        options_count += if (speed *  (time - speed)) > duration { 1 } else { 0 };
+fn calculate_options_dummy(time: u64, duration: u64) -> u64 {
+    let mut options_count: u64 = 0;
+    for idx in (1..time.saturating_sub(1)).rev() {
+        let speed: u64 = time - idx;
+        options_count += if speed.saturating_mul(time - speed) > duration { 1 } else { 0 };
     }
     return options_count;
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let inputs: Vec<Vec<u32>> = input.split("\n").map(|line| {
-        let numbers: Vec<u32> = line.split(":").last().unwrap().split(" ").filter_map(|it| {
-            match it {
-                "" => None,
-                num => Some(num.trim().parse::<u32>().unwrap())
-            }
-        }).collect();
-        return numbers;
-    }).collect();
-
-    let times: Vec<u32> = inputs.first().unwrap().to_owned();
-    let durations: Vec<u32> = inputs.last().unwrap().to_owned();
-
-    let result = times.iter().zip(durations.iter())
-        .map(|tuple| calculate_options_dummy(*tuple.0, *tuple.1))
-        .fold(1, |acc, it| acc * it);
-
-    return result.to_string();
-}
-
 fn calculate_options_smart(time: u64, duration: u64) -> u64 {
-    let discriminant_root: f64 = ((time.pow(2) - 4 * duration) as f64).sqrt();
+    let discriminant: u128 = match (time as u128).pow(2).checked_sub(4 * duration as u128) {
+        Some(discriminant) => discriminant,
+        None => return 0,
+    };
+    let discriminant_root: f64 = (discriminant as f64).sqrt();
     let min_x: f64 = (time as f64 - discriminant_root) / 2f64;
     let max_x: f64 = (time as f64 + discriminant_root) / 2f64;
     return (max_x.trunc() - min_x.trunc()) as u64;
 }
 
-pub fn solve_part2(input: &str) -> String {
-    let inputs: Vec<u64> = input.split("\n").map(|line| {
-        let number: String = line.split(":").last().unwrap().split(" ").filter_map(|it| {
-            match it {
-                "" => None,
-                num => Some(num.trim())
-            }
-        }).intersperse("").collect();
-        return number.parse::<u64>().unwrap();
-    }).collect();
-
-    let result: u64 = calculate_options_smart(*inputs.first().unwrap(), *inputs.last().unwrap());
-    return result.to_string();
+const INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+pub struct Races {
+    /// Time and record distance of every race
+    races: Vec<(u64, u64)>,
+    /// Single race read with the spaces between the numbers ignored
+    single: (u64, u64),
+}
+
+impl Races {
+    fn create<'a>(parser: &Parser<'a>, input: &'a str) -> Result<Races, ParseError> {
+        let lines: Vec<&str> = parser.lines().collect();
+        let [time_line, distance_line] = lines[..] else {
+            return Err(parser.error(input, "expected a time line and a distance line"));
+        };
+
+        let (_, time_numbers) = parser.key_value(time_line, ":")?;
+        let (_, distance_numbers) = parser.key_value(distance_line, ":")?;
+        let times: Vec<u64> = parser.numbers(time_numbers)?;
+        let distances: Vec<u64> = parser.numbers(distance_numbers)?;
+        if times.is_empty() || times.len() != distances.len() {
+            return Err(parser.error(distance_line, format!("expected {} distance(s), one per time", times.len().max(1))));
+        }
+
+        let concat = |numbers: &'a str| -> Result<u64, ParseError> {
+            return numbers.split_whitespace().collect::<String>().parse::<u64>()
+                .map_err(|err| parser.error(numbers, format!("joined number is invalid: {}", err)));
+        };
+
+        return Ok(Races {
+            races: times.into_iter().zip(distances).collect(),
+            single: (concat(time_numbers)?, concat(distance_numbers)?),
+        });
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Races;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return Races::create(&parser, input);
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u64 = input.races.iter()
+            .map(|(time, duration)| calculate_options_dummy(*time, *duration))
+            .product();
+
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u64 = calculate_options_smart(input.single.0, input.single.1);
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
@@ -66,18 +91,28 @@ mod tests {
     use std::assert_eq;
     use super::*;
 
-    const INPUT: &str = "Time:      7  15   30
-Distance:  9  40  200";
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day06::solve_part1(INPUT).unwrap();
         assert_eq!(result, "288");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day06::solve_part2(INPUT).unwrap();
         assert_eq!(result, "71503");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_error() {
+        assert!(Day06::parse("").is_err());
+        assert!(Day06::parse("Time: 7 15\nDistance: 9").is_err());
+        assert_eq!(Day06::solve_part1("Time: 7 200000\nDistance: 9 5000000000").unwrap(), "565684");
+        assert_eq!(Day06::solve_part1("Time: 0\nDistance: 0").unwrap(), "0");
+    }
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day06>();
+    }
+}
@@ -1,17 +1,33 @@
 use std::cmp::Ordering;
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::u64;
 
-use clap::Parser;
+use common::error::SolveError;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 use itertools::Itertools;
-use lazy_static::lazy_static;
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
+/// Cards from the lowest to the highest, hands store cards as their index.
+const CARDS: &str = "23456789TJQKA";
+const JOKER: u8 = 9;
+
+pub struct Play {
+    cards: [u8; 5],
+    bid: u32,
+}
+
+impl Play {
+    fn create<'a>(parser: &Parser<'a>, input: &'a str) -> Result<Play, ParseError> {
+        let (cards, bid) = parser.key_value(input, " ")?;
+        if let Some((idx, _)) = cards.char_indices().find(|(_, it)| !CARDS.contains(*it)) {
+            return Err(parser.error(&cards[idx..], "expected a card"));
+        }
+        let cards: [u8; 5] = cards.bytes()
+            .map(|card| CARDS.bytes().position(|it| it == card).unwrap_or_default() as u8)
+            .collect::<Vec<u8>>()
+            .try_into()
+            .map_err(|_| parser.error(cards, "expected 5 cards"))?;
+
+        return Ok(Play { cards, bid: parser.value(bid)? });
+    }
 }
 
 struct Hand {
@@ -20,105 +36,46 @@ struct Hand {
     base13: u64,
 }
 
-lazy_static! {
-    static ref RANKS: HashMap<&'static str, u8> = vec![
-        ("11111", 0),
-        ("1111J", 1),
-        ("2111",1),
-        ("221", 2),
-        ("211J", 3),
-        ("111JJ", 3),
-        ("311", 3),
-        ("22J", 4),
-        ("32", 4),
-        ("11JJJ", 5),
-        ("21JJ", 5),
-        ("31J", 5),
-        ("41", 5),
-        ("JJJJJ", 6),
-        ("1JJJJ", 6),
-        ("2JJJ", 6),
-        ("3JJ", 6),
-        ("4J", 6),
-        ("5", 6),
-    ].into_iter().collect();
-
-    static ref PUZZLE1_BASE13: HashMap<u8, u8> = "23456789TJQKA".bytes()
-        .enumerate()
-        .map(|(idx, ch)| (ch, idx as u8))
-        .collect();
-
-    static ref PUZZLE2_BASE13: HashMap<u8, u8> = "J23456789TQKA".bytes()
-        .enumerate()
-        .map(|(idx, ch)| (ch, idx as u8))
-        .collect();
-}
-
 impl Hand {
-    fn create(input: &str) -> Hand {
-        let parts: Vec<&str> = input.split(' ').collect();
-
-        let mut cards: Vec<u8> = parts.first().unwrap().as_bytes().iter().map(|it| *it).collect();
-        let base13: u64 = Hand::decode(cards.clone(), &PUZZLE1_BASE13);
-
-        cards.sort();
-
-        let mut sizes: Vec<usize> = cards.iter().group_by(|b| b.to_owned()).into_iter()
-            .map(|it| it.1.count())
-            .collect();
-        sizes.sort_by(|a, b| b.cmp(a));
-
-        let rank_key: String = sizes.iter().map(|it| it.to_string()).intersperse("".to_owned()).collect();
-
-        return Hand {
-            bid: parts.last().unwrap().parse().unwrap(),
-            rank: RANKS.get(rank_key.as_str()).unwrap().to_owned(),
-            base13,
-        };
+    fn create(play: &Play) -> Hand {
+        return Hand::rate(play.bid, &play.cards, play.cards, 0);
     }
 
-    fn create_with_jokers(input: &str) -> Hand {
-        let parts: Vec<&str> = input.split(' ').collect();
-
-        let cards: Vec<u8> = parts.first().unwrap().as_bytes().iter().map(|it| *it).collect();
-        let base13: u64 = Hand::decode(cards.clone(), &PUZZLE2_BASE13);
-
-        let mut no_joker_cards: Vec<u8> = cards.iter().filter(|it| *it != &b'J').map(|it| it.to_owned()).collect();
-        let jokers_count: u8 = (5 - no_joker_cards.len()) as u8;
-
-        no_joker_cards.sort();
+    /// Hand where jokers act as the best card for the type, but are the weakest card on ties.
+    fn create_with_jokers(play: &Play) -> Hand {
+        let values: [u8; 5] = play.cards.map(|it| match it {
+            JOKER => 0,
+            it if it < JOKER => it + 1,
+            it => it,
+        });
+        let others: Vec<u8> = play.cards.iter().copied().filter(|it| *it != JOKER).collect();
+        return Hand::rate(play.bid, &others, values, 5 - others.len());
+    }
 
-        let mut sizes: Vec<usize> = no_joker_cards.iter().group_by(|b| b.to_owned()).into_iter()
-            .map(|it| it.1.count())
-            .collect();
+    /// Jokers join the largest group of the other cards, ties are broken by the values of the cards in order.
+    fn rate(bid: u32, grouped: &[u8], values: [u8; 5], jokers: usize) -> Hand {
+        let mut sizes: Vec<usize> = grouped.iter().counts().into_values().collect();
         sizes.sort_by(|a, b| b.cmp(a));
 
-        let rank_key: String = sizes.iter()
-            .map(|it| it.to_string())
-            .merge(std::iter::repeat('J'.to_string()).take(jokers_count as usize))
-            .intersperse("".to_owned())
-            .collect();
+        let largest: usize = sizes.first().copied().unwrap_or(0) + jokers;
+        let second: usize = sizes.get(1).copied().unwrap_or(0);
+        let rank: u8 = match (largest, second) {
+            (5, _) => 6,
+            (4, _) => 5,
+            (3, 2) => 4,
+            (3, _) => 3,
+            (2, 2) => 2,
+            (2, _) => 1,
+            _ => 0,
+        };
 
         return Hand {
-            bid: parts.last().unwrap().parse().unwrap(),
-            rank: RANKS.get(rank_key.as_str()).unwrap().to_owned(),
-            base13,
+            bid,
+            rank,
+            base13: values.iter().fold(0, |acc, it| acc * CARDS.len() as u64 + *it as u64),
         };
     }
 
-    fn decode(cards: Vec<u8>, base: &HashMap<u8, u8>) -> u64 {
-        let base_len: usize = base.len();
-
-        let mut reversed = cards.clone();
-        reversed.reverse();
-
-        return reversed.iter()
-            .enumerate()
-            .fold(0, |acc, (idx, ch)| {
-                return acc + base.get(ch).unwrap().to_owned() as usize * base_len.pow(idx as u32);
-            }) as u64;
-    }
-
     fn compare(&self, other: &Hand) -> Ordering {
         if self.rank > other.rank {
             return Ordering::Greater;
@@ -150,7 +107,7 @@ impl PartialEq<Self> for Hand {
 
 impl PartialOrd<Self> for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        return Some(self.compare(other));
+        return Some(self.cmp(other));
     }
 }
 
@@ -160,41 +117,60 @@ impl Ord for Hand {
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let mut hands: Vec<Hand> = input.split("\n").map(|line| Hand::create(line)).collect();
-    hands.sort();
+const INPUT: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+pub struct Day07;
 
-    let result: u64 = hands.iter().enumerate().fold(0, |acc, (idx, hand)| acc + (idx + 1) * hand.bid as usize ) as u64;
-    return result.to_string();
-}
+impl Solution for Day07 {
+    type Input = Vec<Play>;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.lines().map(|line| Play::create(&parser, line)).collect();
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let mut hands: Vec<Hand> = input.iter().map(Hand::create).collect();
+        hands.sort();
 
-pub fn solve_part2(input: &str) -> String {
-    let mut hands: Vec<Hand> = input.split("\n").map(|line| Hand::create_with_jokers(line)).collect();
-    hands.sort();
+        let result: u64 = hands.iter().enumerate().fold(0, |acc, (idx, hand)| acc + (idx + 1) * hand.bid as usize ) as u64;
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let mut hands: Vec<Hand> = input.iter().map(Hand::create_with_jokers).collect();
+        hands.sort();
 
-    let result: u64 = hands.iter().enumerate().fold(0, |acc, (idx, hand)| acc + (idx + 1) * hand.bid as usize ) as u64;
-    return result.to_string();
+        let result: u64 = hands.iter().enumerate().fold(0, |acc, (idx, hand)| acc + (idx + 1) * hand.bid as usize ) as u64;
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day07::solve_part1(INPUT).unwrap();
         assert_eq!(result, "6440");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day07::solve_part2(INPUT).unwrap();
         assert_eq!(result, "5905");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day07>();
+    }
+}
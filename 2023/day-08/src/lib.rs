@@ -1,97 +1,96 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
 
-use clap::Parser;
-
-use common::math::lcm_vec;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
+use common::error::SolveError;
+use common::cycle::{self, Cycle};
+use common::math;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
+use itertools::Itertools;
 
 enum LR {
     L,
     R,
 }
 
-struct Map {
+pub struct Map {
     instructions: Vec<LR>,
-    network: HashMap<String, (String, String)>,
+    names: Vec<String>,
+    /// Indices of the left and the right node of every node
+    links: Vec<(usize, usize)>,
 }
 
-impl Map {
-    fn create(input: &str) -> Map {
-        let lines: Vec<&str> = input.splitn(2, "\n").collect();
-
-        return Map {
-            instructions: lines.first().unwrap().chars().map(|it| if it == 'L' { LR::L } else { LR::R }).collect(),
-            network: lines.last().unwrap().trim().split('\n').map(|it| {
-                let record: Vec<&str> = it.split(" = ").collect();
-                let coords: Vec<&str> = record.last().unwrap().split(",").collect();
-                (
-                    record.first().unwrap().trim().to_owned(),
-                    (
-                        coords.first().unwrap().strip_prefix("(").unwrap().trim().to_owned(),
-                        coords.last().unwrap().strip_suffix(")").unwrap().trim().to_owned()
-                    )
-                )
-            }).collect(),
-        };
-    }
-
-    fn next_instruction(&self, steps_count: &u64) -> &LR {
-        return self.instructions.get((steps_count % self.instructions.len() as u64) as usize).unwrap();
-    }
+/// Steps at which a ghost stands on a `..Z` node.
+struct Ghost {
+    cycle: Cycle,
+    /// `..Z` flags of the states up to the repeated one
+    finished: Vec<bool>,
 }
 
-
-pub fn solve_part1(input: &str) -> String {
-    let map: Map = Map::create(input);
-
-    let mut node: &str = "AAA";
-    let mut steps_count: u64 = 0;
-
-    while node != "ZZZ" {
-        match map.next_instruction(&steps_count) {
-            LR::L => node = map.network.get(node).unwrap().0.as_str(),
-            LR::R => node = map.network.get(node).unwrap().1.as_str(),
-        }
-        steps_count += 1;
+impl Ghost {
+    fn is_finished(&self, steps_count: usize) -> bool {
+        return self.finished[self.cycle.index(steps_count)];
     }
 
-    return steps_count.to_string();
+    /// Steps of the `..Z` states on the cycle, each one repeating with the period.
+    fn finishes(&self) -> Vec<(i64, i64)> {
+        return (self.cycle.start..self.cycle.start + self.cycle.period)
+            .filter(|it| self.finished[*it])
+            .map(|it| (it as i64, self.cycle.period as i64))
+            .collect();
+    }
 }
 
-pub fn solve_part2(input: &str) -> String {
-    let map: Map = Map::create(input);
+impl Map {
+    fn create(input: &str) -> Result<Map, ParseError> {
+        let parser: Parser = Parser::create(input);
+        let [instructions, network] = parser.fields(input, "\n\n")?;
+
+        let instructions: &str = instructions.trim();
+        let instructions: Vec<LR> = instructions.char_indices().map(|(idx, it)| match it {
+            'L' => Ok(LR::L),
+            'R' => Ok(LR::R),
+            _ => Err(parser.error(&instructions[idx..], "expected 'L' or 'R'")),
+        }).collect::<Result<_, _>>()?;
+        let nodes: Vec<(&str, &str, &str, &str)> = network.lines().map(|it| {
+            let (node, next) = parser.key_value(it, "=")?;
+            let next: &str = parser.strip_suffix(parser.strip_prefix(next, "(")?, ")")?;
+            let (left, right) = parser.key_value(next, ",")?;
+            return Ok((it, node, left, right));
+        }).collect::<Result<_, _>>()?;
+
+        let index: HashMap<&str, usize> = nodes.iter().enumerate().map(|(idx, it)| (it.1, idx)).collect();
+        let links: Vec<(usize, usize)> = nodes.iter().map(|(line, _, left, right)| {
+            let find = |name: &str| index.get(name).copied()
+                .ok_or_else(|| parser.error(line, format!("node '{}' is not in the network", name)));
+            return Ok((find(left)?, find(right)?));
+        }).collect::<Result<_, ParseError>>()?;
+        let names: Vec<String> = nodes.iter().map(|it| it.1.to_string()).collect();
+
+        return Ok(Map { instructions, names, links });
+    }
 
-    let nodes: Vec<&str> = map.network.keys().filter_map(|it| if it.ends_with("A") { Some(it.as_str()) } else { None }).collect();
-    let steps_count: Vec<u64> = nodes.iter().map(|it| {
-        let mut node: &str = it;
-        let mut steps_count: u64 = 0;
+    fn next(&self, node: usize, steps_count: usize) -> usize {
+        return match self.instructions[steps_count % self.instructions.len()] {
+            LR::L => self.links[node].0,
+            LR::R => self.links[node].1,
+        };
+    }
 
-        while !node.ends_with("Z") {
-            match map.next_instruction(&steps_count) {
-                LR::L => node = map.network.get(node).unwrap().0.as_str(),
-                LR::R => node = map.network.get(node).unwrap().1.as_str(),
-            }
-            steps_count += 1;
-        }
-        return steps_count;
-    }).collect();
+    /// Walks from the node until a node is reached again at the same position in the instructions, the states are
+    /// every node reachable from the start with the instruction position.
+    fn walk(&self, start: usize) -> (Cycle, Vec<(usize, usize)>) {
+        let len: usize = self.instructions.len();
+        return cycle::find((start, 0), |&(node, idx)| (self.next(node, idx), (idx + 1) % len));
+    }
 
-    let result: u64 = lcm_vec(&steps_count).unwrap();
-    return result.to_string();
+    fn find_ghost(&self, start: usize) -> Ghost {
+        let (cycle, states) = self.walk(start);
+        let finished: Vec<bool> = states.iter().map(|(node, _)| self.names[*node].ends_with('Z')).collect();
+        return Ghost { cycle, finished };
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT1: &str = "RL
+const INPUT1: &str = "RL
 
 AAA = (BBB, CCC)
 BBB = (DDD, EEE)
@@ -101,13 +100,7 @@ EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)";
 
-    const INPUT2: &str = "LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
-
-    const INPUT3: &str = "LR
+const INPUT3: &str = "LR
 
 11A = (11B, XXX)
 11B = (XXX, 11Z)
@@ -118,18 +111,93 @@ ZZZ = (ZZZ, ZZZ)";
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Map;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT1, INPUT3];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        return Map::create(input);
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let Some(start) = input.names.iter().position(|it| it == "AAA") else {
+            return Err(SolveError::NoSolution("network has no node 'AAA'".to_string()));
+        };
+
+        let (_, states) = input.walk(start);
+        let steps_count: usize = states.iter().position(|(node, _)| input.names[*node] == "ZZZ")
+            .ok_or_else(|| SolveError::NoSolution("node 'ZZZ' is not reachable".to_string()))?;
+
+        return Ok(Answer::from(steps_count));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let ghosts: Vec<Ghost> = (0..input.names.len())
+            .filter(|it| input.names[*it].ends_with('A'))
+            .map(|it| input.find_ghost(it))
+            .collect();
+        let no_solution = || SolveError::NoSolution("ghosts never meet".to_string());
+
+        // Steps before every ghost is on its cycle are checked one by one, the later ones by the cycles
+        let prefix: usize = ghosts.iter().map(|it| it.cycle.start).max().ok_or_else(no_solution)?;
+        if let Some(result) = (0..prefix).find(|it| ghosts.iter().all(|ghost| ghost.is_finished(*it))) {
+            return Ok(Answer::from(result));
+        }
+
+        let result: i64 = ghosts.iter()
+            .map(|it| it.finishes())
+            .multi_cartesian_product()
+            .filter_map(|cycles| math::first_common(&cycles))
+            .min()
+            .ok_or_else(no_solution)?;
+        return Ok(Answer::from(result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT2: &str = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT1);
+        let result = Day08::solve_part1(INPUT1).unwrap();
         assert_eq!(result, "2");
 
-        let result = solve_part1(INPUT2);
+        let result = Day08::solve_part1(INPUT2).unwrap();
         assert_eq!(result, "6");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT3);
+        let result = Day08::solve_part2(INPUT3).unwrap();
         assert_eq!(result, "6");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_no_solution() {
+        let input: &str = "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n22A = (22Z, 22Z)\n22Z = (22B, 22B)\n22B = (22Z, 22Z)";
+        assert!(Day08::solve_part2(input).is_err());
+
+        let input: &str = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)";
+        assert!(Day08::solve_part1(input).is_err());
+        assert!(Day08::solve_part2(input).is_err());
+
+        let input: &str = "L\n\nAAA = (BBZ, BBZ)\nBBZ = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)";
+        assert!(matches!(Day08::solve_part1(input), Err(SolveError::NoSolution(_))));
+    }
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day08>();
+    }
+}
@@ -1,87 +1,66 @@
-use std::path::PathBuf;
+use common::error::SolveError;
+use common::poly;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
-use clap::Parser;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
-struct History {
-    data: Vec<Vec<i32>>,
+pub struct History {
+    data: Vec<Vec<i64>>,
 }
 
 impl History {
-    fn create(input: &str) -> History {
-        return History {
-            data: input.split('\n').map(|line| {
-                return line.split(' ').map(|it| it.trim().parse::<i32>().unwrap()).collect();
-            }).collect()
-        };
+    fn create(input: &str) -> Result<History, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return Ok(History {
+            data: parser.lines().map(|line| parser.numbers(line)).collect::<Result<_, _>>()?
+        });
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let history: History = History::create(input);
-
-    let result: i32 = history.data.iter().map(|line| {
-        let mut path: Vec<i32> = Vec::new();
-        let mut step: Vec<i32> = line.clone();
-
-        loop {
-            path.push(*step.last().unwrap());
-            step = step.windows(2).map(|it| it.last().unwrap() - it.first().unwrap()).collect();
-            if step.iter().all(|it| it == &0) {
-                break;
-            }
-        }
+const INPUT: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
 
-        return path.iter().fold(0, |acc, it| acc + *it);
-    }).sum();
+pub struct Day09;
 
-    return result.to_string();
-}
+impl Solution for Day09 {
+    type Input = History;
+    type Params = ();
 
-pub fn solve_part2(input: &str) -> String {
-    let history: History = History::create(input);
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
 
-    let result: i32 = history.data.iter().map(|line| {
-        let mut path: Vec<i32> = Vec::new();
-        let mut step: Vec<i32> = line.clone();
-
-        loop {
-            path.push(*step.first().unwrap());
-            step = step.windows(2).map(|it| it.last().unwrap() - it.first().unwrap()).collect();
-            if step.iter().all(|it| it == &0) {
-                break;
-            }
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        return History::create(input);
+    }
 
-        return path.iter().rev().fold(0, |acc, it| *it - acc);
-    }).sum();
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: i128 = input.data.iter().map(|line| poly::extrapolate(line, line.len() as i64)).sum();
+        return Ok(Answer::from(result));
+    }
 
-    return result.to_string();
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: i128 = input.data.iter().map(|line| poly::extrapolate(line, -1)).sum();
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day09::solve_part1(INPUT).unwrap();
         assert_eq!(result, "114");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day09::solve_part2(INPUT).unwrap();
         assert_eq!(result, "2");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day09>();
+    }
+}
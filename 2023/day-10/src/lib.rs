@@ -1,16 +1,8 @@
-use std::cmp::Ordering;
-use std::collections::HashSet;
-use std::path::PathBuf;
-
-use clap::Parser;
-use ndarray::Array2;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
+use common::error::SolveError;
+use common::geom::{self, Direction, Position};
+use common::grid::Grid;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
 #[derive(Debug, PartialEq, Clone)]
 enum Pipe {
@@ -41,200 +33,150 @@ impl Pipe {
         return vec![Pipe::NS, Pipe::EW, Pipe::NE, Pipe::NW, Pipe::SE, Pipe::SW];
     }
 
-    fn connections(&self, pos: [usize; 2]) -> Vec<[i32; 2]> {
+    /// Directions to the two pipes connected to this one.
+    fn connections(&self) -> Vec<Direction> {
         return match self {
             Pipe::START => Vec::new(),
-            Pipe::NS => vec![[pos[0] as i32 - 1, pos[1] as i32], [pos[0] as i32 + 1, pos[1] as i32]], // UP + DOWN
-            Pipe::EW => vec![[pos[0] as i32, pos[1] as i32 - 1], [pos[0] as i32, pos[1] as i32 + 1]], // LEFT + RIGHT
-            Pipe::NE => vec![[pos[0] as i32 - 1, pos[1] as i32], [pos[0] as i32, pos[1] as i32 + 1]], // UP + RIGHT
-            Pipe::NW => vec![[pos[0] as i32 - 1, pos[1] as i32], [pos[0] as i32, pos[1] as i32 - 1]], // UP + LEFT
-            Pipe::SE => vec![[pos[0] as i32 + 1, pos[1] as i32], [pos[0] as i32, pos[1] as i32 + 1]], // DOWN + RIGHT
-            Pipe::SW => vec![[pos[0] as i32 + 1, pos[1] as i32], [pos[0] as i32, pos[1] as i32 - 1]], // DOWN + LEFT
-        };
-    }
-
-    fn is_vertical(&self) -> bool {
-        return match self {
-            Pipe::NS => true,
-            Pipe::SW => true,
-            Pipe::SE => true,
-            _ => false
+            Pipe::NS => vec![Direction::Up, Direction::Down],
+            Pipe::EW => vec![Direction::Left, Direction::Right],
+            Pipe::NE => vec![Direction::Up, Direction::Right],
+            Pipe::NW => vec![Direction::Up, Direction::Left],
+            Pipe::SE => vec![Direction::Down, Direction::Right],
+            Pipe::SW => vec![Direction::Down, Direction::Left],
         };
     }
 }
 
-#[derive(Debug)]
-struct Grid {
-    data: Array2<Option<Pipe>>,
+#[derive(Debug, Clone)]
+pub struct Sketch {
+    grid: Grid<Option<Pipe>>,
 }
 
-impl Grid {
-    fn create(input: &str) -> Grid {
-        let data: Vec<Vec<Option<Pipe>>> = input.split('\n')
-            .map(|line| line.chars().map(|it| Pipe::create(it)).collect())
-            .collect();
-
-        return Grid {
-            data: Array2::from_shape_vec(
-                (data.len(), data.first().unwrap().len()),
-                data.into_iter().flatten().collect(),
-            ).unwrap()
-        };
-    }
-
-    fn cmp_pos(pos1: &[usize; 2], pos2: &[usize; 2]) -> Ordering {
-        return [pos1[1], pos1[0]].cmp(&[pos2[1], pos2[0]]);
-    }
-
-    fn find(&self, item: Option<Pipe>) -> Option<[usize; 2]> {
-        return self.data.indexed_iter().find_map(|it| {
-            if *it.1 == item {
-                return Some([it.0.0, it.0.1]);
-            }
-            return None;
-        });
+impl Sketch {
+    fn create(input: &str) -> Result<Sketch, ParseError> {
+        let parser: Parser = Parser::create(input);
+        let grid: Grid<Option<Pipe>> = parser.grid(input, |it| if it == '.' { Some(None) } else { Pipe::create(it).map(Some) })?;
+        return Ok(Sketch { grid });
     }
 
-    fn belongs(&self, pos: &[i32; 2]) -> bool {
-        let shape = self.data.shape();
-        return pos[0] >= 0 && pos[0] < shape[0] as i32 && pos[1] >= 0 && pos[1] < shape[1] as i32;
+    fn pipe_connections(&self, pipe: &Pipe, pos: Position) -> Vec<Position> {
+        return pipe.connections().into_iter().map(|it| pos.step(it)).filter(|it| self.grid.is_inside(*it)).collect();
     }
 
-    fn connections(&self, pos: [usize; 2]) -> Vec<[usize; 2]> {
-        return match self.data[pos].as_ref() {
-            Some(pipe) => pipe.connections(pos).iter().filter_map(|it| {
-                if self.belongs(it) {
-                    return Some([it[0] as usize, it[1] as usize]);
-                }
-                return None;
-            }).collect(),
+    fn connections(&self, pos: Position) -> Vec<Position> {
+        return match self.grid[pos].as_ref() {
+            Some(pipe) => self.pipe_connections(pipe, pos),
             None => Vec::new(),
         };
     }
 
-    fn detect_start_pipe(&self, start_pos: &[usize; 2]) -> Pipe {
-        let directions: Vec<[i32; 2]> = vec![
-            // UP    RIGHT   DOWN    LEFT
-            [-1, 0], [0, 1], [1, 0], [0, -1],
-        ];
-
-        let mut start_connections: Vec<[usize; 2]> = directions.iter().filter_map(|it| {
-            if !self.belongs(&[start_pos[0] as i32 + it[0], start_pos[1] as i32 + it[1]]) {
-                return None;
-            }
-
-            let pos: [usize; 2] = [(start_pos[0] as i32 + it[0]) as usize, (start_pos[1] as i32 + it[1]) as usize];
-
-            return if self.connections(pos).contains(start_pos) { Some(pos) } else { None };
-        }).collect();
+    fn detect_start_pipe(&self, start_pos: &Position) -> Option<Pipe> {
+        let mut start_connections: Vec<Position> = start_pos.neighbours4()
+            .filter(|pos| self.grid.is_inside(*pos) && self.connections(*pos).contains(start_pos))
+            .collect();
 
-        start_connections.sort_by(|a, b| Grid::cmp_pos(a, b));
+        start_connections.sort();
 
         return Pipe::candidates().iter().find_map(|it| {
-            let mut connections: Vec<[usize; 2]> = it.connections(*start_pos).iter()
-                .filter_map(|it| if self.belongs(it) { Some([it[0] as usize, it[1] as usize]) } else { None })
-                .collect();
-            connections.sort_by(|a, b| Grid::cmp_pos(a, b));
+            let mut connections: Vec<Position> = self.pipe_connections(it, *start_pos);
+            connections.sort();
             return if connections == start_connections { Some(it.clone()) } else { None };
-        }).expect("Failed to detect start pipe");
+        });
+    }
+
+    /// Sketch with the start tile replaced by the pipe connecting its neighbours, and the start position.
+    fn with_start_pipe(&self) -> Result<(Sketch, Position), SolveError> {
+        let start_pos: Position = Position::from(
+            self.grid.position_of(&Some(Pipe::START)).ok_or_else(|| SolveError::NoSolution("animal position not found".to_string()))?
+        );
+        let start_pipe: Pipe = self.detect_start_pipe(&start_pos)
+            .ok_or_else(|| SolveError::NoSolution("start tile does not connect exactly two pipes".to_string()))?;
+
+        let mut sketch: Sketch = self.clone();
+        sketch.grid[start_pos] = Some(start_pipe);
+        return Ok((sketch, start_pos));
     }
 
-    fn find_loop(&self, start_pos: [usize; 2]) -> Vec<[usize; 2]> {
-        let mut prev: [usize; 2] = start_pos.clone();
-        let mut pos = self.connections(start_pos).first().unwrap().clone();
-        let mut main_loop: Vec<[usize; 2]> = vec![start_pos];
+    /// Tiles of the loop through the start, `NoSolution` when a pipe on the way does not connect back.
+    fn find_loop(&self, start_pos: Position) -> Result<Vec<Position>, SolveError> {
+        let broken = |pos: Position| SolveError::NoSolution(format!("loop is broken at line {}, column {}", pos.row + 1, pos.col + 1));
+        let mut prev: Position = start_pos;
+        let mut pos: Position = *self.connections(start_pos).first().ok_or_else(|| broken(start_pos))?;
+        let mut main_loop: Vec<Position> = vec![start_pos];
 
-        loop {
-            if pos == start_pos {
-                break;
-            }
+        while pos != start_pos {
             main_loop.push(pos);
 
-            let connections: Vec<[usize; 2]> = self.connections(pos);
-            let next_pos: [usize; 2] = if connections[0] == prev { connections[1] } else { connections[0] };
+            let next_pos: Position = match self.connections(pos)[..] {
+                [first, second] if first == prev => second,
+                [first, second] if second == prev => first,
+                _ => return Err(broken(pos)),
+            };
 
             prev = pos;
             pos = next_pos;
         }
 
-        return main_loop;
+        return Ok(main_loop);
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let mut grid: Grid = Grid::create(input);
-
-    let start_pos: [usize; 2] = grid.find(Some(Pipe::START)).expect("Animal position not found!");
-    let start_pipe: Pipe = grid.detect_start_pipe(&start_pos);
-
-    grid.data[start_pos] = Some(start_pipe);
-
-    let result: usize = grid.find_loop(start_pos).len() / 2;
+const INPUT1A: &str = ".....
+.S-7.
+.|.|.
+.L-J.
+.....";
 
-    return result.to_string();
-}
+const INPUT2A: &str = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
 
-pub fn solve_part2(input: &str) -> String {
-    let mut grid: Grid = Grid::create(input);
+pub struct Day10;
 
-    let start_pos: [usize; 2] = grid.find(Some(Pipe::START)).expect("Animal position not found!");
-    let start_pipe: Pipe = grid.detect_start_pipe(&start_pos);
+impl Solution for Day10 {
+    type Input = Sketch;
+    type Params = ();
 
-    grid.data[start_pos] = Some(start_pipe);
+    const EXAMPLES: &'static [&'static str] = &[INPUT1A, INPUT2A];
 
-    let main_loop: HashSet<[usize; 2]> = HashSet::from_iter(grid.find_loop(start_pos));
-    let shape: &[usize] = grid.data.shape();
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        return Sketch::create(input);
+    }
 
-    let mut enclosed_count: usize = 0;
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let (sketch, start_pos) = input.with_start_pipe()?;
+        let result: usize = sketch.find_loop(start_pos)?.len() / 2;
 
-    for idx in 0..shape[0] {
-        let mut enclosed: bool = false;
-        for idy in 0..shape[1] {
-            let pos: [usize; 2] = [idx, idy];
+        return Ok(Answer::from(result));
+    }
 
-            let pipe = grid.data[pos].as_ref();
-            let is_vertical = pipe.map(|pipe| pipe.is_vertical()).or(Some(false)).unwrap();
-            let in_loop: bool = main_loop.contains(&pos);
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let (sketch, start_pos) = input.with_start_pipe()?;
 
-            if in_loop && is_vertical {
-                enclosed = !enclosed;
-            }
+        // the tiles of the loop are the vertices of a polygon, the enclosed tiles are the lattice points inside it
+        let main_loop: Vec<Position> = sketch.find_loop(start_pos)?;
+        let enclosed_count: i64 = geom::interior_count(&main_loop);
 
-            if !in_loop && enclosed {
-                enclosed_count += 1;
-            }
-        }
+        return Ok(Answer::from(enclosed_count));
     }
-
-    return enclosed_count.to_string();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT1A: &str = ".....
-.S-7.
-.|.|.
-.L-J.
-.....";
-
     const INPUT1B: &str = "..F7.
 .FJ|.
 SJ.L7
 |F--J
 LJ...";
 
-    const INPUT2A: &str = "...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........";
-
     const INPUT2B: &str = ".F----7F7F7F7F-7....
 .|F--7||||||||FJ....
 .||.FJ||||||||L7....
@@ -246,7 +188,6 @@ L--J.L7...LJS7F-7L7.
 ....FJL-7.||.||||...
 ....L---J.LJ.LJLJ...";
 
-
     const INPUT2C: &str = "FF7FSF7F7F7F7F7F---7
 L|LJ||||||||||||F--J
 FL-7LJLJ||||||LJL-77
@@ -260,22 +201,33 @@ L7JLJL-JLJLJL--JLJ.L";
 
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT1A);
+        let result = Day10::solve_part1(INPUT1A).unwrap();
         assert_eq!(result, "4");
 
-        let result = solve_part1(INPUT1B);
+        let result = Day10::solve_part1(INPUT1B).unwrap();
         assert_eq!(result, "8");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT2A);
+        let result = Day10::solve_part2(INPUT2A).unwrap();
         assert_eq!(result, "4");
 
-        let result = solve_part2(INPUT2B);
+        let result = Day10::solve_part2(INPUT2B).unwrap();
         assert_eq!(result, "8");
 
-        let result = solve_part2(INPUT2C);
+        let result = Day10::solve_part2(INPUT2C).unwrap();
         assert_eq!(result, "10");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_broken_loop() {
+        let result = Day10::solve_part1("S-.\n|..\n...");
+        assert!(matches!(result, Err(SolveError::NoSolution(_))));
+    }
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day10>();
+    }
+}
@@ -1,36 +1,22 @@
 use std::cmp::{max, min};
-use std::path::PathBuf;
 
-use clap::Parser;
-use ndarray::Array2;
+use common::error::SolveError;
+use common::grid::Grid;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
-struct Galaxy {
-    data: Array2<bool>,
+pub struct Galaxy {
+    data: Grid<bool>,
 }
 
 impl Galaxy {
-    fn create(input: &str) -> Galaxy {
-        let data: Vec<Vec<bool>> = input.split('\n')
-            .map(|line| line.chars().map(|it| it == '#').collect())
-            .collect();
-
-        return Galaxy {
-            data: Array2::from_shape_vec(
-                (data.len(), data.first().unwrap().len()),
-                data.into_iter().flatten().collect(),
-            ).unwrap()
-        };
+    fn create(input: &str) -> Result<Galaxy, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return Ok(Galaxy { data: parser.grid(input, |it| matches!(it, '#' | '.').then_some(it == '#'))? });
     }
 
     fn find_empty_rows(&self) -> Vec<usize> {
-        return self.data.rows().into_iter()
+        return self.data.rows()
             .enumerate()
             .filter_map(|it| {
                 if it.1.iter().all(|it| !*it) {
@@ -41,10 +27,10 @@ impl Galaxy {
     }
 
     fn find_empty_columns(&self) -> Vec<usize> {
-        return self.data.columns().into_iter()
+        return self.data.columns()
             .enumerate()
-            .filter_map(|it| {
-                if it.1.iter().all(|it| !*it) {
+            .filter_map(|mut it| {
+                if it.1.all(|it| !*it) {
                     return Some(it.0);
                 }
                 return None;
@@ -52,61 +38,32 @@ impl Galaxy {
     }
 
     fn find_planets(&self) -> Vec<[usize; 2]> {
-        return self.data.indexed_iter().filter_map(|it| {
-            if *it.1 {
-                return Some([it.0.0 as usize, it.0.1 as usize]);
-            }
-            return None;
-        }).collect();
+        return self.data.find_all(|it| *it).map(|it| [it.0, it.1]).collect();
     }
 
-    fn find_paths_sum(&self, planets: &Vec<[usize; 2]>, empty_rows: &Vec<usize>, empty_cols: &Vec<usize>, scale: usize) -> usize {
-        return planets.clone().into_iter().enumerate().map(|it| {
+    fn find_paths_sum(&self, planets: &[[usize; 2]], empty_rows: &[usize], empty_cols: &[usize], scale: usize) -> usize {
+        return planets.iter().enumerate().map(|it| {
             return planets.iter().take(it.0).map(|p| {
                 let mut distances: usize = 0;
-                distances = distances + (min(it.1[0], p[0])..max(it.1[0], p[0])).map(|row_idx| {
+                distances += (min(it.1[0], p[0])..max(it.1[0], p[0])).map(|row_idx| {
                     if empty_rows.contains(&row_idx) {
                         return scale;
                     }
                     return 1;
-                }).fold(0, |acc, x| acc + x);
-                distances = distances + (min(it.1[1], p[1])..max(it.1[1], p[1])).map(|col_idx| {
+                }).sum::<usize>();
+                distances += (min(it.1[1], p[1])..max(it.1[1], p[1])).map(|col_idx| {
                     if empty_cols.contains(&col_idx) {
                         return scale;
                     }
                     return 1;
-                }).fold(0, |acc, x| acc + x);
+                }).sum::<usize>();
                 return distances;
-            }).fold(0, |acc, x| acc + x);
-        }).fold(0, |acc, x| acc + x);
+            }).sum::<usize>();
+        }).sum();
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let galaxy: Galaxy = Galaxy::create(input);
-
-    let planets: Vec<[usize;2]> = galaxy.find_planets();
-    let empty_rows: Vec<usize> = galaxy.find_empty_rows();
-    let empty_cols: Vec<usize> = galaxy.find_empty_columns();
-
-    return galaxy.find_paths_sum(&planets, &empty_rows, &empty_cols, 2).to_string();
-}
-
-pub fn solve_part2(input: &str) -> String {
-    let galaxy: Galaxy = Galaxy::create(input);
-
-    let planets: Vec<[usize;2]> = galaxy.find_planets();
-    let empty_rows: Vec<usize> = galaxy.find_empty_rows();
-    let empty_cols: Vec<usize> = galaxy.find_empty_columns();
-
-    return galaxy.find_paths_sum(&planets, &empty_rows, &empty_cols, 1000000).to_string();
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = "...#......
+const INPUT: &str = "...#......
 .......#..
 #.........
 ..........
@@ -117,16 +74,53 @@ mod tests {
 .......#..
 #...#.....";
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Galaxy;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        return Galaxy::create(input);
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let planets: Vec<[usize;2]> = input.find_planets();
+        let empty_rows: Vec<usize> = input.find_empty_rows();
+        let empty_cols: Vec<usize> = input.find_empty_columns();
+
+        return Ok(Answer::from(input.find_paths_sum(&planets, &empty_rows, &empty_cols, 2)));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let planets: Vec<[usize;2]> = input.find_planets();
+        let empty_rows: Vec<usize> = input.find_empty_rows();
+        let empty_cols: Vec<usize> = input.find_empty_columns();
+
+        return Ok(Answer::from(input.find_paths_sum(&planets, &empty_rows, &empty_cols, 1000000)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day11::solve_part1(INPUT).unwrap();
         assert_eq!(result, "374");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day11::solve_part2(INPUT).unwrap();
         assert_eq!(result, "82000210");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day11>();
+    }
+}
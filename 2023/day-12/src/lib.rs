@@ -1,14 +1,8 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
-
-use clap::Parser;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
+use common::error::SolveError;
+use common::memo::Memo;
+use common::par;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
 #[derive(Debug, Clone, Copy)]
 enum LineItem {
@@ -17,136 +11,135 @@ enum LineItem {
     Unknown,
 }
 
-#[derive(Debug)]
-struct DamagedLine {
+#[derive(Debug, Clone)]
+pub struct DamagedLine {
     line: Vec<LineItem>,
     sizes: Vec<u32>,
-    cache: HashMap<(usize, i32, usize), u64>,
 }
 
+/// Position in the line, then the remaining size of the current group (negative outside of one) and the next group.
+type Arrangements = Memo<(usize, i32, usize), u64>;
+
 impl DamagedLine {
-    fn create_single(input: &str) -> DamagedLine {
-        let parts: Vec<&str> = input.split(' ').collect();
-        return DamagedLine {
-            line: parts.first().unwrap().chars().map(|it| match it {
-                '?' => LineItem::Unknown,
-                '.' => LineItem::Separator,
-                '#' => LineItem::Spring,
-                _ => panic!("Unexpected input")
-            }).collect(),
-            sizes: parts.last().unwrap().split(',').map(|it| it.parse::<u32>().unwrap()).collect(),
-            cache: HashMap::new(),
-        };
+    fn create_single<'a>(parser: &Parser<'a>, input: &'a str) -> Result<DamagedLine, ParseError> {
+        let (line, sizes) = parser.key_value(input, " ")?;
+        return Ok(DamagedLine {
+            line: line.char_indices().map(|(idx, it)| match it {
+                '?' => Ok(LineItem::Unknown),
+                '.' => Ok(LineItem::Separator),
+                '#' => Ok(LineItem::Spring),
+                _ => Err(parser.error(&line[idx..], "expected '?', '.' or '#'")),
+            }).collect::<Result<_, _>>()?,
+            sizes: parser.numbers(sizes)?,
+        });
     }
 
-    fn create_unfolded(input: &str, factor: usize) -> DamagedLine {
-        let mut single: DamagedLine = DamagedLine::create_single(input);
-        single.line.push(LineItem::Unknown);
+    fn unfold(&self, factor: usize) -> DamagedLine {
+        let mut line: Vec<LineItem> = self.line.clone();
+        line.push(LineItem::Unknown);
 
         return DamagedLine {
-            line: single.line.iter()
+            line: line.iter()
                 .cycle()
-                .take(single.line.len() * factor - 1)
-                .map(|it| *it)
+                .take(line.len() * factor - 1)
+                .copied()
                 .collect(),
-            sizes: single.sizes.iter()
+            sizes: self.sizes.iter()
                 .cycle()
-                .take(single.sizes.len() * factor)
-                .map(|it| *it)
+                .take(self.sizes.len() * factor)
+                .copied()
                 .collect(),
-            cache: HashMap::new(),
         };
     }
 
-    fn handle_separator(&mut self, line_pos: usize, size_pos: (i32, usize)) -> u64 {
+    fn handle_separator(&self, memo: &mut Arrangements, line_pos: usize, size_pos: (i32, usize)) -> u64 {
         return match size_pos.0 <= 0 {
-            true => self.calc_arrangements(line_pos + 1, (-1, size_pos.1)),
+            true => self.calc_arrangements(memo, line_pos + 1, (-1, size_pos.1)),
             false => 0
         };
     }
 
-    fn handle_spring(&mut self, line_pos: usize, size_pos: (i32, usize)) -> u64 {
+    fn handle_spring(&self, memo: &mut Arrangements, line_pos: usize, size_pos: (i32, usize)) -> u64 {
         return match size_pos.0 > 0 {
-            true => self.calc_arrangements(line_pos + 1, (size_pos.0 - 1, size_pos.1)),
+            true => self.calc_arrangements(memo, line_pos + 1, (size_pos.0 - 1, size_pos.1)),
             false => match size_pos.0 < 0 && size_pos.1 < self.sizes.len() {
-                true => self.calc_arrangements(line_pos + 1, (self.sizes[size_pos.1] as i32 - 1, size_pos.1 + 1)),
+                true => self.calc_arrangements(memo, line_pos + 1, (self.sizes[size_pos.1] as i32 - 1, size_pos.1 + 1)),
                 false => 0
             }
         };
     }
 
-    fn calc_arrangements(&mut self, line_pos: usize, size_pos: (i32, usize)) -> u64 {
-        let result_cache_key = (line_pos, size_pos.0, size_pos.1);
-
-        // check if result already calculated
-        if self.cache.contains_key(&result_cache_key) {
-            return *self.cache.get(&result_cache_key).unwrap();
-        }
+    /// Number of ways the unknown items can be filled in to match the group sizes.
+    fn arrangements(&self) -> u64 {
+        let mut memo: Arrangements = Memo::new();
+        return self.calc_arrangements(&mut memo, 0, (-1, 0));
+    }
 
+    fn calc_arrangements(&self, memo: &mut Arrangements, line_pos: usize, size_pos: (i32, usize)) -> u64 {
         // reach end of line ?
         if line_pos == self.line.len() {
             return if size_pos.0 > 0 || size_pos.1 != self.sizes.len() { 0 } else { 1 };
         }
 
-        let mut result: u64 = 0;
+        return memo.get_or_compute((line_pos, size_pos.0, size_pos.1), |memo, _| {
+            return match self.line[line_pos] {
+                LineItem::Unknown => self.handle_separator(memo, line_pos, size_pos) + self.handle_spring(memo, line_pos, size_pos),
+                LineItem::Separator => self.handle_separator(memo, line_pos, size_pos),
+                LineItem::Spring => self.handle_spring(memo, line_pos, size_pos),
+            };
+        });
+    }
+}
 
-        match self.line[line_pos] {
-            LineItem::Unknown => {
-                result += self.handle_separator(line_pos, size_pos);
-                result += self.handle_spring(line_pos, size_pos);
-            }
+const INPUT: &str = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
 
-            LineItem::Separator => {
-                result += self.handle_separator(line_pos, size_pos);
-            }
+pub struct Day12;
 
-            LineItem::Spring => {
-                result += self.handle_spring(line_pos, size_pos);
-            }
-        }
+impl Solution for Day12 {
+    type Input = Vec<DamagedLine>;
+    type Params = ();
 
-        self.cache.insert(result_cache_key, result);
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
 
-        return result;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.lines().map(|it| DamagedLine::create_single(&parser, it)).collect();
     }
-}
 
-pub fn solve_part1(input: &str) -> String {
-    let result: i64 = input.split('\n')
-        .map(|line| DamagedLine::create_single(line))
-        .map(|mut it| it.calc_arrangements(0, (-1, 0)))
-        .fold(0, |acc, it| acc + it as i64);
-    return result.to_string();
-}
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u64 = par::map(input, |line| line.arrangements()).into_iter().sum();
+        return Ok(Answer::from(result));
+    }
 
-pub fn solve_part2(input: &str) -> String {
-    let result: i64 = input.split('\n')
-        .map(|line| DamagedLine::create_unfolded(line, 5))
-        .map(|mut it| it.calc_arrangements(0, (-1, 0)))
-        .fold(0, |acc, it| acc + it as i64);
-    return result.to_string();
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u64 = par::map(input, |line| line.unfold(5).arrangements()).into_iter().sum();
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day12::solve_part1(INPUT).unwrap();
         assert_eq!(result, "21");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day12::solve_part2(INPUT).unwrap();
         assert_eq!(result, "525152");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day12>();
+    }
+}
@@ -1,38 +1,23 @@
 use std::cmp::min;
-use std::path::PathBuf;
 use std::str::FromStr;
 
-use anyhow::Error;
-use clap::Parser;
+use common::error::SolveError;
+use common::grid::Grid;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 use itertools::{FoldWhile, Itertools};
-use ndarray::Array2;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
 
 #[derive(Debug)]
-struct Pattern {
-    data: Array2<bool>
+pub struct Pattern {
+    data: Grid<bool>
 }
 
 impl FromStr for Pattern {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let data: Vec<Vec<bool>> = input.split('\n')
-            .map(|line| line.chars().map(|it| it == '#').collect())
-            .collect();
-
-        return Ok(Pattern {
-            data: Array2::from_shape_vec(
-                (data.len(), data.first().unwrap().len()),
-                data.into_iter().flatten().collect(),
-            ).unwrap()
-        });
+        let parser: Parser = Parser::create(input);
+        return Ok(Pattern { data: parser.grid(input, |it| matches!(it, '#' | '.').then_some(it == '#'))? });
     }
 }
 
@@ -42,16 +27,16 @@ impl Pattern {
             if acc > max_diff {
                 return FoldWhile::Done(acc);
             }
-            return if *it.1 != self.data[[idx2, it.0]] { FoldWhile::Continue(acc + 1) } else { FoldWhile::Continue(acc) };
+            return if *it.1 != self.data[(idx2, it.0)] { FoldWhile::Continue(acc + 1) } else { FoldWhile::Continue(acc) };
         }).into_inner();
     }
 
     fn compare_columns(&self, idx1: usize, idx2: usize, max_diff: u32) -> u32 {
-        return self.data.column(idx1).iter().enumerate().fold_while(0, |acc, it| {
+        return self.data.column(idx1).enumerate().fold_while(0, |acc, it| {
             if acc > max_diff {
                 return FoldWhile::Done(acc);
             }
-            return if *it.1 != self.data[[it.0, idx2]] { FoldWhile::Continue(acc + 1) } else { FoldWhile::Continue(acc) };
+            return if *it.1 != self.data[(it.0, idx2)] { FoldWhile::Continue(acc + 1) } else { FoldWhile::Continue(acc) };
         }).into_inner();
     }
 
@@ -76,31 +61,31 @@ impl Pattern {
         return result <= max_diff;
     }
 
-    fn get_reflection_score(&self) -> u32 {
-        let shape = self.data.shape();
+    fn get_reflection_score(&self) -> Option<u32> {
+        let shape: [usize; 2] = [self.data.height(), self.data.width()];
 
         for idx in 1..shape[0] {
             let size = min(idx, shape[0] - idx);
             if self.has_rows_reflection(idx, size, 0) {
-                return idx as u32 * 100;
+                return Some(idx as u32 * 100);
             }
         }
 
         for idx in 1..shape[1] {
             let size = min(idx, shape[1] - idx);
             if self.has_columns_reflection(idx, size, 0) {
-                return idx as u32;
+                return Some(idx as u32);
             }
         }
 
-        panic!("No reflection found: {:?}", self);
+        return None;
     }
 
-    fn get_smudge_score(&self) -> u32 {
-        let shape = self.data.shape();
+    fn get_smudge_score(&self) -> Option<u32> {
+        let shape: [usize; 2] = [self.data.height(), self.data.width()];
 
         for idx in 0..shape[1] - 1 {
-            let max_diff = (0..shape[1]).into_iter().fold_while(0, |acc, idy| {
+            let max_diff = (0..shape[1]).fold_while(0, |acc, idy| {
                 if acc > 1 {
                     return FoldWhile::Done(acc);
                 }
@@ -116,12 +101,12 @@ impl Pattern {
             }).into_inner();
 
             if max_diff == 1 {
-                return idx as u32 + 1;
+                return Some(idx as u32 + 1);
             }
         }
 
         for idx in 0..shape[0] - 1 {
-            let max_diff = (0..shape[0]).into_iter().fold_while(0, |acc, idy| {
+            let max_diff = (0..shape[0]).fold_while(0, |acc, idy| {
                 if acc > 1 {
                     return FoldWhile::Done(acc);
                 }
@@ -137,37 +122,15 @@ impl Pattern {
             }).into_inner();
 
             if max_diff == 1 {
-                return (idx as u32 + 1) * 100;
+                return Some((idx as u32 + 1) * 100);
             }
         }
 
-        panic!("No smudge found: {:?}", self);
+        return None;
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let patterns: Vec<Pattern> = input.split("\n\n")
-        .map(|it| it.parse::<Pattern>().unwrap())
-        .collect();
-    let result = patterns.iter()
-        .fold(0, |acc, pattern| acc + pattern.get_reflection_score());
-    return result.to_string();
-}
-
-pub fn solve_part2(input: &str) -> String {
-    let patterns: Vec<Pattern> = input.split("\n\n")
-        .map(|it| it.parse::<Pattern>().unwrap())
-        .collect();
-    let result = patterns.iter()
-        .fold(0, |acc, pattern| acc + pattern.get_smudge_score());
-    return result.to_string();
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = "#.##..##.
+const INPUT: &str = "#.##..##.
 ..#.##.#.
 ##......#
 ##......#
@@ -183,15 +146,54 @@ mod tests {
 ..##..###
 #....#..#";
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Pattern>;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.blocks(input).into_iter()
+            .map(|it| parser.parse::<Pattern>(it))
+            .collect();
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u32 = input.iter()
+            .map(|pattern| pattern.get_reflection_score().ok_or_else(|| SolveError::NoSolution("pattern has no reflection".to_string())))
+            .sum::<Result<u32, SolveError>>()?;
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u32 = input.iter()
+            .map(|pattern| pattern.get_smudge_score().ok_or_else(|| SolveError::NoSolution("pattern has no smudge".to_string())))
+            .sum::<Result<u32, SolveError>>()?;
+        return Ok(Answer::from(result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day13::solve_part1(INPUT).unwrap();
         assert_eq!(result, "405");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day13::solve_part2(INPUT).unwrap();
         assert_eq!(result, "400");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day13>();
+    }
+}
@@ -1,21 +1,11 @@
-use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
-use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
 use std::str::FromStr;
 
-use anyhow::Error;
-use clap::Parser;
-use itertools::{FoldWhile, Itertools};
-use ndarray::{Array2, ArrayViewMut1};
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
+use common::error::SolveError;
+use common::grid::Grid;
+use common::cycle;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 enum PlatformItem {
@@ -42,55 +32,50 @@ impl Debug for PlatformItem {
     }
 }
 
-#[derive(Debug, Clone, Hash)]
-struct Platform {
-    data: Array2<PlatformItem>,
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    data: Grid<PlatformItem>,
 }
 
 impl FromStr for Platform {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let data: Vec<Vec<PlatformItem>> = input.split('\n')
-            .map(|line| line.chars()
-                .map(|it| match it {
-                    'O' => PlatformItem::RoundRock,
-                    '#' => PlatformItem::CubeRock,
-                    '.' => PlatformItem::EmptySpace,
-                    it => panic!("Invalid platform item: {}", it)
-                }).collect()
-            ).collect();
-
-        return Ok(Platform {
-            data: Array2::from_shape_vec(
-                (data.len(), data.first().unwrap().len()),
-                data.into_iter().flatten().collect(),
-            ).unwrap()
-        });
+        let parser: Parser = Parser::create(input);
+        let data: Grid<PlatformItem> = parser.grid(input, |it| match it {
+            'O' => Some(PlatformItem::RoundRock),
+            '#' => Some(PlatformItem::CubeRock),
+            '.' => Some(PlatformItem::EmptySpace),
+            _ => None,
+        })?;
+
+        return Ok(Platform { data });
     }
 }
 
 impl Platform {
     fn tilt(&self, direction: TiltDirection) -> Platform {
         let mut titled: Platform = self.clone();
-        let shape = titled.data.shape();
+        let (height, width) = (titled.data.height(), titled.data.width());
 
         match direction {
             TiltDirection::North | TiltDirection::South => {
-                for idx in 0..shape[1] {
+                for idx in 0..width {
+                    let column: Vec<(usize, usize)> = (0..height).map(|row| (row, idx)).collect();
                     if direction == TiltDirection::North {
-                        Platform::tilt_forward(titled.data.column_mut(idx))
+                        Platform::tilt_forward(&mut titled.data, &column)
                     } else {
-                        Platform::tilt_backward(titled.data.column_mut(idx))
+                        Platform::tilt_backward(&mut titled.data, &column)
                     }
                 }
             }
             TiltDirection::West | TiltDirection::East => {
-                for idx in 0..shape[0] {
+                for idx in 0..height {
+                    let row: Vec<(usize, usize)> = (0..width).map(|col| (idx, col)).collect();
                     if direction == TiltDirection::West {
-                        Platform::tilt_forward(titled.data.row_mut(idx))
+                        Platform::tilt_forward(&mut titled.data, &row)
                     } else {
-                        Platform::tilt_backward(titled.data.row_mut(idx))
+                        Platform::tilt_backward(&mut titled.data, &row)
                     }
                 }
             }
@@ -106,13 +91,14 @@ impl Platform {
             .tilt(TiltDirection::East);
     }
 
-    fn tilt_forward(mut view: ArrayViewMut1<PlatformItem>) {
+    /// Moves round rocks of the line of positions towards its start.
+    fn tilt_forward(data: &mut Grid<PlatformItem>, view: &[(usize, usize)]) {
         let mut next_pos: usize = 0;
         for idx in 0..view.len() {
-            match view[idx] {
+            match data[view[idx]] {
                 PlatformItem::CubeRock => next_pos = idx + 1,
                 PlatformItem::RoundRock => {
-                    view.swap(next_pos, idx);
+                    data.swap(view[next_pos], view[idx]);
                     next_pos += 1;
                 }
                 _ => {}
@@ -120,13 +106,14 @@ impl Platform {
         }
     }
 
-    fn tilt_backward(mut view: ArrayViewMut1<PlatformItem>) {
+    /// Moves round rocks of the line of positions towards its end.
+    fn tilt_backward(data: &mut Grid<PlatformItem>, view: &[(usize, usize)]) {
         let mut next_pos: i32 = view.len() as i32 - 1;
         for idx in (0..view.len()).rev() {
-            match view[idx] {
+            match data[view[idx]] {
                 PlatformItem::CubeRock => next_pos = idx as i32 - 1,
                 PlatformItem::RoundRock => {
-                    view.swap(next_pos as usize, idx);
+                    data.swap(view[next_pos as usize], view[idx]);
                     next_pos -= 1;
                 }
                 _ => {}
@@ -135,84 +122,72 @@ impl Platform {
     }
 
     fn total_load(&self) -> u32 {
-        self.data.columns().into_iter().fold(0, |acc, column| {
-            return acc + column.indexed_iter().fold(0, |acc, (pos, it)| {
+        let height: u32 = self.data.height() as u32;
+        self.data.columns().fold(0, |acc, column| {
+            return acc + column.enumerate().fold(0, |acc, (pos, it)| {
                 return if *it == PlatformItem::RoundRock {
-                    acc + column.len() as u32 - pos as u32
+                    acc + height - pos as u32
                 } else {
                     acc
                 };
             });
         })
     }
-
-    fn get_hash(&self) -> u64 {
-        let mut hasher = DefaultHasher::new();
-        self.hash(&mut hasher);
-        return hasher.finish();
-    }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let platform: Platform = input.parse::<Platform>().unwrap();
-    let result = platform.tilt(TiltDirection::North).total_load();
-    return result.to_string();
-}
-
-pub fn solve_part2(input: &str) -> String {
-    let mut cache: HashMap<u64, (usize, Platform)> = HashMap::new();
-
-    let platform: Platform = input.parse::<Platform>().unwrap();
-    cache.insert(platform.get_hash(), (0, platform.clone()));
+const INPUT: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
 
-    let mut titled: Platform = platform;
-    let cycles_count: usize = 1000000000;
+pub struct Day14;
 
-    let position = (1..cycles_count).fold_while(0, |acc, idx| {
-        titled = titled.tilt_cycle();
+impl Solution for Day14 {
+    type Input = Platform;
+    type Params = ();
 
-        let hash: u64 = titled.get_hash();
-        if cache.contains_key(&hash) {
-            let existing = cache.get(&hash).unwrap();
-            let position = (cycles_count - (*existing).0) % (idx - (*existing).0) + (*existing).0;
-            return FoldWhile::Done(position);
-        }
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
 
-        cache.insert(hash, (idx, titled.clone()));
-        return FoldWhile::Continue(acc);
-    }).into_inner();
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.parse::<Platform>(input);
+    }
 
-    let result: u32 = cache.iter()
-        .find(|it| it.to_owned().1.to_owned().0 == position)
-        .expect("Failed to find resulted platform").1.to_owned().1.total_load();
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result = input.tilt(TiltDirection::North).total_load();
+        return Ok(Answer::from(result));
+    }
 
-    return result.to_string();
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u32 = cycle::state_at(input.clone(), |it| it.tilt_cycle(), 1_000_000_000).total_load();
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#....";
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day14::solve_part1(INPUT).unwrap();
         assert_eq!(result, "136");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day14::solve_part2(INPUT).unwrap();
         assert_eq!(result, "64");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day14>();
+    }
+}
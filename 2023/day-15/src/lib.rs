@@ -1,71 +1,107 @@
-use std::path::PathBuf;
-
-use clap::Parser;
+use common::error::SolveError;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 use linked_hash_map::LinkedHashMap;
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
 fn make_hash(input: &str) -> u32 {
     return input.bytes().fold(0, |acc, code| ((acc + code as u32) * 17) % 256);
 }
 
-fn parse_step(input: &str) -> (&str, &str) {
-    let parts: Vec<&str> = input.split(['-', '=']).collect();
-    return (parts.first().unwrap(), parts.last().unwrap());
+const INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+pub enum Operation {
+    Remove,
+    Insert(u32),
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let result: u32 = input.split(',').fold(0, |acc, it| acc + make_hash(it));
-    return result.to_string();
+pub struct Step {
+    /// Whole step as written, which part 1 hashes
+    text: String,
+    label: String,
+    op: Operation,
 }
 
-pub fn solve_part2(input: &str) -> String {
-    let mut boxes: Vec<LinkedHashMap<&str, u32>> = (0..256).into_iter().map(|_| LinkedHashMap::new()).collect();
-
-    input.split(',').for_each(|step| {
-        let (label, focal) = parse_step(step);
-        let hash: u32 = make_hash(label);
-        if !focal.is_empty() {
-            let storage = boxes.get_mut(hash as usize).unwrap();
-            if storage.contains_key(label) {
-                storage[label] = focal.parse::<u32>().unwrap();
-            } else {
-                storage.insert(label, focal.parse::<u32>().unwrap());
+impl Step {
+    fn create<'a>(parser: &Parser<'a>, input: &'a str) -> Result<Step, ParseError> {
+        let (label, op) = if let Some(label) = input.strip_suffix('-') {
+            (label, Operation::Remove)
+        } else if let Some((label, focal)) = input.split_once('=') {
+            (label, Operation::Insert(parser.value::<u32>(focal)?))
+        } else {
+            return Err(parser.error(input, "expected '=' or '-'"));
+        };
+        if label.is_empty() || !label.chars().all(|it| it.is_ascii_lowercase()) {
+            return Err(parser.error(input, "expected a label of lowercase letters"));
+        }
+        return Ok(Step { text: input.to_string(), label: label.to_string(), op });
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<Step>;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return input.trim().split(',').map(|step| Step::create(&parser, step)).collect();
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: u32 = input.iter().fold(0, |acc, it| acc + make_hash(&it.text));
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let mut boxes: Vec<LinkedHashMap<&str, u32>> = (0..256).map(|_| LinkedHashMap::new()).collect();
+
+        for step in input {
+            let storage = &mut boxes[make_hash(&step.label) as usize];
+            match step.op {
+                Operation::Insert(focal) => {
+                    if let Some(lens) = storage.get_mut(step.label.as_str()) {
+                        *lens = focal;
+                    } else {
+                        storage.insert(&step.label, focal);
+                    }
+                }
+                Operation::Remove => {
+                    storage.remove(step.label.as_str());
+                }
             }
-        } else if boxes[hash as usize].contains_key(label) {
-            boxes.get_mut(hash as usize).unwrap().remove(label);
         }
-    });
 
-    let result: usize = boxes.iter().enumerate().fold(0, |acc, (idx, storage)| {
-        return acc + storage.iter().enumerate().fold(0, |acc, (idy, label)| {
-            return acc + (idx + 1) * (idy + 1) * (*label.1 as usize);
+        let result: usize = boxes.iter().enumerate().fold(0, |acc, (idx, storage)| {
+            return acc + storage.iter().enumerate().fold(0, |acc, (idy, label)| {
+                return acc + (idx + 1) * (idy + 1) * (*label.1 as usize);
+            });
         });
-    });
 
-    return result.to_string();
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day15::solve_part1(INPUT).unwrap();
         assert_eq!(result, "1320");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day15::solve_part2(INPUT).unwrap();
         assert_eq!(result, "145");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day15>();
+    }
+}
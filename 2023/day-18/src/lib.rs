@@ -1,18 +1,11 @@
 extern crate core;
 
 use std::fmt::{Debug, Formatter};
-use std::path::PathBuf;
 
-use clap::Parser;
-use polygonical::point::Point;
-use polygonical::polygon::Polygon;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
+use common::error::SolveError;
+use common::geom::{self, Position};
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
 enum DigDirection {
     UP,
@@ -33,108 +26,81 @@ impl Debug for DigDirection {
 }
 
 impl DigDirection {
-    fn from_str(input: &str) -> DigDirection {
+    fn from_str(input: &str) -> Option<DigDirection> {
         return match input {
-            "U" => DigDirection::UP,
-            "R" => DigDirection::RIGHT,
-            "D" => DigDirection::DOWN,
-            "L" => DigDirection::LEFT,
-            _ => panic!("Invalid direction: {}", input)
+            "U" => Some(DigDirection::UP),
+            "R" => Some(DigDirection::RIGHT),
+            "D" => Some(DigDirection::DOWN),
+            "L" => Some(DigDirection::LEFT),
+            _ => None
         };
     }
 
-    fn from_num(input: &u8) -> DigDirection {
+    fn from_num(input: &str) -> Option<DigDirection> {
         return match input {
-            0 => DigDirection::RIGHT,
-            1 => DigDirection::DOWN,
-            2 => DigDirection::LEFT,
-            3 => DigDirection::UP,
-            _ => panic!("Invalid direction: {}", input)
+            "0" => Some(DigDirection::RIGHT),
+            "1" => Some(DigDirection::DOWN),
+            "2" => Some(DigDirection::LEFT),
+            "3" => Some(DigDirection::UP),
+            _ => None
         };
     }
 
-    fn dig(&self, length: u64) -> (i64, i64) {
+    fn dig(&self, length: u64) -> Position {
         return match self {
-            DigDirection::UP => (length as i64 * -1, 0),
-            DigDirection::RIGHT => (0, length as i64),
-            DigDirection::DOWN => (length as i64, 0),
-            DigDirection::LEFT => (0, length as i64 * -1),
+            DigDirection::UP => Position::create(-(length as i64), 0),
+            DigDirection::RIGHT => Position::create(0, length as i64),
+            DigDirection::DOWN => Position::create(length as i64, 0),
+            DigDirection::LEFT => Position::create(0, -(length as i64)),
         };
     }
 }
 
 #[derive(Debug)]
-struct DigInstruction {
+pub struct DigInstruction {
     direction: DigDirection,
     length: u64,
 }
 
 impl DigInstruction {
-    fn create(input: &str) -> DigInstruction {
-        let parts: Vec<&str> = input.split(" ").take(2).collect();
-        return DigInstruction {
-            direction: DigDirection::from_str(parts.first().unwrap()),
-            length: parts.last().unwrap().parse::<u64>().unwrap(),
-        };
+    fn create<'a>(parser: &Parser<'a>, direction: &'a str, length: &'a str) -> Result<DigInstruction, ParseError> {
+        return Ok(DigInstruction {
+            direction: DigDirection::from_str(direction).ok_or_else(|| parser.error(direction, "expected 'U', 'R', 'D' or 'L'"))?,
+            length: parser.value(length)?,
+        });
     }
 
-    fn decode(input: &str) -> DigInstruction {
-        let hex: &str = input.split(" ").last().unwrap().trim_matches(|it| it == '(' || it == ')' || it == '#');
-        return DigInstruction {
-            direction: DigDirection::from_num(&hex[5..].parse::<u8>().unwrap()),
-            length: u64::from_str_radix(&hex[..5], 16).unwrap(),
-        };
+    fn decode<'a>(parser: &Parser<'a>, color: &'a str) -> Result<DigInstruction, ParseError> {
+        let hex: &str = parser.strip_suffix(parser.strip_prefix(color, "(#")?, ")")?;
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(parser.error(hex, "expected 6 hexadecimal digits"));
+        }
+        return Ok(DigInstruction {
+            direction: DigDirection::from_num(&hex[5..]).ok_or_else(|| parser.error(&hex[5..], "expected a direction from 0 to 3"))?,
+            length: u64::from_str_radix(&hex[..5], 16).map_err(|err| parser.error(hex, err))?,
+        });
     }
 
-    fn dig(&self, from: &Point) -> Point {
-        let (offset_x, offset_y) = self.direction.dig(self.length);
-        return Point { x: from.x + offset_x as f64, y: from.y + offset_y as f64 };
+    fn dig(&self, from: &Position) -> Position {
+        return *from + self.direction.dig(self.length);
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let mut points: Vec<Point> = Vec::new();
-    let (perimeter, _) = input.split('\n').fold((0u64, Point { x: 0f64, y: 0f64 }), |(perimeter, last_point), it| {
-        points.push(last_point);
-        let instruction: DigInstruction = DigInstruction::create(it);
-        return (perimeter + instruction.length, instruction.dig(&last_point));
-    });
-
-    let polygon: Polygon = Polygon::new(points);
-    let area: u64 = polygon.area() as u64;
-
-    // pick theorem
-    let points_inside: u64 = area - (perimeter / 2) + 1;
-
-    let result: u64 = perimeter + points_inside;
-    return result.to_string();
-}
-
-pub fn solve_part2(input: &str) -> String {
-    let mut points: Vec<Point> = Vec::new();
-    let (perimeter, _) = input.split('\n').fold((0u64, Point { x: 0f64, y: 0f64 }), |(perimeter, last_point), it| {
-        points.push(last_point);
-        let instruction: DigInstruction = DigInstruction::decode(it);
-        return (perimeter + instruction.length, instruction.dig(&last_point));
+/// Number of cubic meters of the trench and of the interior dug out by the instructions.
+fn lagoon_size<'a>(instructions: impl Iterator<Item = &'a DigInstruction>) -> Result<i64, SolveError> {
+    let mut corners: Vec<Position> = Vec::new();
+    instructions.fold(Position::ORIGIN, |last_corner, instruction| {
+        corners.push(last_corner);
+        return instruction.dig(&last_corner);
     });
 
-    let polygon: Polygon = Polygon::new(points);
-    let area: u64 = polygon.area() as u64;
-
-    // pick theorem
-    let points_inside: u64 = area - (perimeter / 2) + 1;
-
-    let result: u64 = perimeter + points_inside;
-    return result.to_string();
+    if corners.is_empty() {
+        return Err(SolveError::NoSolution("dig plan is empty".to_string()));
+    }
+    return Ok(geom::boundary_count(&corners) + geom::interior_count(&corners));
 }
 
-#[cfg(test)]
-mod tests {
-    use std::assert_eq;
-
-    use super::*;
-
-    const INPUT: &str = "R 6 (#70c710)
+const INPUT: &str = "R 6 (#70c710)
 D 5 (#0dc571)
 L 2 (#5713f0)
 D 2 (#d2c081)
@@ -149,15 +115,61 @@ U 3 (#a77fa3)
 L 2 (#015232)
 U 2 (#7a21e3)";
 
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<(DigInstruction, DigInstruction)>;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        // every line holds both the plain instruction and the one encoded in the colour
+        return parser.lines()
+            .map(|line| {
+                let [direction, length, color] = parser.fields(line, " ")?;
+                return Ok((DigInstruction::create(&parser, direction, length)?, DigInstruction::decode(&parser, color)?));
+            })
+            .collect();
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: i64 = lagoon_size(input.iter().map(|(instruction, _)| instruction))?;
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: i64 = lagoon_size(input.iter().map(|(_, instruction)| instruction))?;
+        return Ok(Answer::from(result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_eq;
+
+    use super::*;
+
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day18::solve_part1(INPUT).unwrap();
         assert_eq!(result, "62");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day18::solve_part2(INPUT).unwrap();
         assert_eq!(result, "952408144115");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_empty_plan() {
+        assert!(matches!(Day18::solve_part1(""), Err(SolveError::NoSolution(_))));
+    }
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day18>();
+    }
+}
@@ -1,9 +1,7 @@
-use std::fs;
-use clap::Parser;
-use aoc2023_day_19::{Cli, solve_part1};
+use std::process::ExitCode;
 
-fn main() {
-    let args: Cli = Cli::parse();
-    let input: String = fs::read_to_string(args.data_dir.join("input1.txt")).unwrap();
-    println!("{}", solve_part1(&input));
+use aoc2023_day_19::Day19;
+
+fn main() -> ExitCode {
+    return common::cli::run::<Day19>(2023, 19, 1);
 }
\ No newline at end of file
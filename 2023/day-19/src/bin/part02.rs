@@ -1,9 +1,7 @@
-use std::fs;
-use clap::Parser;
-use aoc2023_day_19::{Cli, solve_part2};
+use std::process::ExitCode;
 
-fn main() {
-    let args: Cli = Cli::parse();
-    let input: String = fs::read_to_string(args.data_dir.join("input2.txt")).unwrap();
-    println!("{}", solve_part2(&input));
+use aoc2023_day_19::Day19;
+
+fn main() -> ExitCode {
+    return common::cli::run::<Day19>(2023, 19, 2);
 }
\ No newline at end of file
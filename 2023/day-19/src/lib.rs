@@ -1,24 +1,18 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
-use std::path::PathBuf;
 use std::str::FromStr;
 
-use anyhow::{anyhow, Error};
-use clap::Parser;
+use common::error::SolveError;
+use common::interval::Interval;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 use itertools::Itertools;
 use queues::{IsQueue, Queue};
 
 use crate::Action::Forward;
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
 #[derive(Clone, Copy, Hash, Eq, PartialEq)]
-struct Part<T> {
+pub struct Part<T> {
     x: T,
     m: T,
     a: T,
@@ -32,45 +26,48 @@ impl<T> Debug for Part<T> where T: Debug {
 }
 
 impl FromStr for Part<u32> {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let parser: Parser = Parser::create(input);
         let mut part: Part<u32> = Part { x: 0, m: 0, a: 0, s: 0 };
-        input.trim_matches(|it| it == '{' || it == '}').split(',').for_each(|category| {
-            let parts: Vec<&str> = category.split('=').map(|it| it.trim()).collect();
-            let value: u32 = parts.last().unwrap().parse::<u32>().unwrap();
-            match parts.first().unwrap().parse::<Category>().unwrap() {
-                Category::Extreme => part.x = value,
-                Category::Musical => part.m = value,
-                Category::Aerodynamic => part.a = value,
-                Category::Shiny => part.s = value,
-            }
-        });
+        for category in parser.strip_suffix(parser.strip_prefix(input, "{")?, "}")?.split(',') {
+            let (category, value) = parser.key_value(category, "=")?;
+            *part.get_mut(&parser.parse::<Category>(category)?) = parser.value(value)?;
+        }
         return Ok(part);
     }
 }
 
+impl<T> Part<T> {
+    fn get_mut(&mut self, category: &Category) -> &mut T {
+        return match category {
+            Category::Extreme => &mut self.x,
+            Category::Musical => &mut self.m,
+            Category::Aerodynamic => &mut self.a,
+            Category::Shiny => &mut self.s,
+        };
+    }
+}
+
 impl Part<u32> {
     fn score(&self) -> u32 {
         return self.x + self.m + self.a + self.s;
     }
 }
 
-impl Part<(u32, u32)> {
+impl Part<Interval<u32>> {
+    fn create(min: u32, max: u32) -> Part<Interval<u32>> {
+        let interval: Interval<u32> = Interval::closed(min, max);
+        return Part { x: interval, m: interval, a: interval, s: interval };
+    }
+
     fn score(&self) -> u64 {
-        return [self.x, self.m, self.a, self.s].iter()
-            .fold(1, |acc, it| acc * (it.1 as u64 - it.0 as u64 + 1));
+        return [self.x, self.m, self.a, self.s].iter().fold(1, |acc, it| acc * it.len() as u64);
     }
-}
 
-impl Part<(u32, u32)> {
-    fn create(min: u32, max: u32) -> Part<(u32, u32)> {
-        return Part {
-            x: (min, max),
-            m: (min, max),
-            a: (min, max),
-            s: (min, max),
-        };
+    fn is_empty(&self) -> bool {
+        return [self.x, self.m, self.a, self.s].iter().any(|it| it.is_empty());
     }
 }
 
@@ -93,7 +90,7 @@ impl Debug for Category {
 }
 
 impl FromStr for Category {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         return match input {
@@ -101,7 +98,7 @@ impl FromStr for Category {
             "m" => Ok(Category::Musical),
             "a" => Ok(Category::Aerodynamic),
             "s" => Ok(Category::Shiny),
-            _ => Err(anyhow!("Unexpected category: {}", input))
+            _ => Err(ParseError::at(input, input, format!("unexpected category '{}'", input)))
         };
     }
 }
@@ -121,16 +118,17 @@ impl Debug for Condition {
 }
 
 impl FromStr for Condition {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let value: u32 = input[1..].parse::<u32>().unwrap();
-        let op: char = input.chars().next().unwrap();
-        return match op {
-            '<' => Ok(Condition::LESS(value)),
-            '>' => Ok(Condition::GREATER(value)),
-            _ => Err(anyhow!("Unexpected operation: {}", op))
-        };
+        let parser: Parser = Parser::create(input);
+        if let Some(value) = input.strip_prefix('<') {
+            return Ok(Condition::LESS(parser.value(value)?));
+        }
+        if let Some(value) = input.strip_prefix('>') {
+            return Ok(Condition::GREATER(parser.value(value)?));
+        }
+        return Err(parser.error(input, "expected '<' or '>'"));
     }
 }
 
@@ -142,11 +140,15 @@ impl Condition {
         };
     }
 
-    fn adjust(&self, adjusted: (u32, u32), next: (u32, u32)) -> ((u32, u32), (u32, u32)) {
-        match self {
-            Condition::LESS(than) => ((than + 0, adjusted.1), (next.0, than - 1)),
-            Condition::GREATER(than) => ((adjusted.0, than + 0), (than + 1, next.1))
-        }
+    /// Splits the values into the matching ones and the rest.
+    fn split(&self, values: Interval<u32>) -> (Interval<u32>, Interval<u32>) {
+        return match self {
+            Condition::LESS(than) => values.split_at(*than),
+            Condition::GREATER(than) => {
+                let (rest, matching) = values.split_at(than + 1);
+                (matching, rest)
+            }
+        };
     }
 }
 
@@ -167,7 +169,7 @@ impl Debug for Action {
 }
 
 impl FromStr for Action {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         return Ok(match input {
@@ -178,7 +180,10 @@ impl FromStr for Action {
     }
 }
 
-struct Workflow {
+// parts queued for the next workflows and parts accepted by the current one
+type Adjusted<'a> = (Vec<(&'a str, Part<Interval<u32>>)>, Vec<Part<Interval<u32>>>);
+
+pub struct Workflow {
     conditions: Vec<(Category, Condition, Action)>,
     default_action: Action,
 }
@@ -193,21 +198,24 @@ impl Debug for Workflow {
 }
 
 impl FromStr for Workflow {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = input.split(',').collect();
+        let parser: Parser = Parser::create(input);
+        let (conditions, default_action) = input.rsplit_once(',').unwrap_or(("", input));
         return Ok(Workflow {
-            conditions: parts.iter().take(parts.len() - 1).map(|it| {
-                let (category, condition_action) = it.split_at(1);
-                let parts: Vec<&str> = condition_action.split(':').collect();
-                return (
-                    category.parse::<Category>().unwrap(),
-                    parts.first().unwrap().parse::<Condition>().unwrap(),
-                    parts.last().unwrap().parse::<Action>().unwrap()
-                );
-            }).collect(),
-            default_action: parts.last().unwrap().parse::<Action>().unwrap(),
+            conditions: conditions.split(',').filter(|it| !it.is_empty()).map(|it| {
+                let (condition, action) = parser.key_value(it, ":")?;
+                let Some(category) = condition.get(..1) else {
+                    return Err(parser.error(condition, "expected a category"));
+                };
+                return Ok((
+                    parser.parse::<Category>(category)?,
+                    parser.parse::<Condition>(&condition[1..])?,
+                    parser.parse::<Action>(action)?,
+                ));
+            }).collect::<Result<_, _>>()?,
+            default_action: parser.parse::<Action>(default_action)?,
         });
     }
 }
@@ -225,18 +233,19 @@ impl Workflow {
         }).unwrap_or(&self.default_action);
     }
 
-    fn adjust(&self, part: &Part<(u32, u32)>) -> (Vec<(&str, Part<(u32, u32)>)>, Vec<Part<(u32, u32)>>) {
-        let mut accepted: Vec<Part<(u32, u32)>> = Vec::new();
-        let mut queued: Vec<(&str, Part<(u32, u32)>)> = Vec::new();
+    fn adjust(&self, part: &Part<Interval<u32>>) -> Adjusted<'_> {
+        let mut accepted: Vec<Part<Interval<u32>>> = Vec::new();
+        let mut queued: Vec<(&str, Part<Interval<u32>>)> = Vec::new();
 
-        let mut adjusted: Part<(u32, u32)> = part.clone();
+        let mut adjusted: Part<Interval<u32>> = *part;
         for (category, condition, action) in &self.conditions {
-            let mut next: Part<(u32, u32)> = adjusted.clone();
-            match category {
-                Category::Extreme => (adjusted.x, next.x) = condition.adjust(adjusted.x, next.x),
-                Category::Musical => (adjusted.m, next.m) = condition.adjust(adjusted.m, next.m),
-                Category::Aerodynamic => (adjusted.a, next.a) = condition.adjust(adjusted.a, next.a),
-                Category::Shiny => (adjusted.s, next.s) = condition.adjust(adjusted.s, next.s),
+            let mut next: Part<Interval<u32>> = adjusted;
+            let values: &mut Interval<u32> = adjusted.get_mut(category);
+            let (matching, rest) = condition.split(*values);
+            *values = rest;
+            *next.get_mut(category) = matching;
+            if next.is_empty() {
+                continue;
             }
             match action {
                 Action::Accept => accepted.push(next),
@@ -245,6 +254,9 @@ impl Workflow {
             }
         }
 
+        if adjusted.is_empty() {
+            return (queued, accepted);
+        }
         match &self.default_action {
             Action::Accept => accepted.push(adjusted),
             Forward(next_workflow) => queued.push((next_workflow, adjusted)),
@@ -255,79 +267,52 @@ impl Workflow {
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let parts: Vec<&str> = input.split("\n\n").collect();
-    let workflows: HashMap<&str, Workflow> = parts.first().unwrap().split('\n').fold(
-        HashMap::new(),
-        |mut acc, line| {
-            let parts: Vec<&str> = line.split('{').collect();
-            acc.insert(
-                *parts.first().unwrap(),
-                parts.last().unwrap().strip_suffix("}").unwrap().parse::<Workflow>().unwrap(),
-            );
-            return acc;
-        },
-    );
-
-    let parts: Vec<Part<u32>> = parts.last().unwrap().split('\n')
-        .map(|line| line.parse::<Part<u32>>().unwrap())
-        .collect();
-
-    let result = parts.iter().fold(0, |acc, part| {
-        let mut next_action: &Action = workflows.get("in").unwrap().run(part);
-        loop {
-            match next_action {
-                Action::Accept => {
-                    return acc + part.score();
-                }
-                Action::Reject => break,
-                Forward(next_workflow) => {
-                    next_action = workflows.get(next_workflow.as_str()).unwrap().run(part);
-                }
-            }
-        }
-        return acc;
-    });
-
-    return result.to_string();
+pub struct System {
+    workflows: HashMap<String, Workflow>,
+    parts: Vec<Part<u32>>,
 }
 
-pub fn solve_part2(input: &str) -> String {
-    let workflows: HashMap<&str, Workflow> = input.split('\n').fold(
-        HashMap::new(),
-        |mut acc, line| {
-            let parts: Vec<&str> = line.split('{').collect();
-            acc.insert(
-                *parts.first().unwrap(),
-                parts.last().unwrap().strip_suffix("}").unwrap().parse::<Workflow>().unwrap(),
-            );
-            return acc;
-        },
-    );
+impl FromStr for System {
+    type Err = ParseError;
 
-    let mut queue: Queue<(&str, Part<(u32, u32)>)> = Queue::new();
-    queue.add(("in", Part::create(1, 4000))).unwrap();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parser: Parser = Parser::create(s);
+        let blocks: Vec<&str> = parser.blocks(s);
+        let lines: Vec<&str> = blocks.first().copied().unwrap_or(s).lines().collect();
+        let workflows: HashMap<String, Workflow> = lines.iter().map(|line| {
+            let (name, workflow) = parser.key_value(line, "{")?;
+            return Ok((name.to_string(), parser.parse::<Workflow>(parser.strip_suffix(workflow, "}")?)?));
+        }).collect::<Result<_, ParseError>>()?;
 
-    let mut accepted: HashSet<Part<(u32, u32)>> = HashSet::new();
+        if !workflows.contains_key("in") {
+            return Err(parser.error(s, "workflow 'in' is not defined"));
+        }
+        for line in lines {
+            let (name, _) = parser.key_value(line, "{")?;
+            let actions = workflows[name].conditions.iter().map(|(_, _, action)| action).chain([&workflows[name].default_action]);
+            for action in actions {
+                match action {
+                    Forward(next) if !workflows.contains_key(next) => {
+                        return Err(parser.error(line, format!("workflow '{}' is not defined", next)));
+                    }
+                    _ => {}
+                }
+            }
+        }
 
-    while queue.size() > 0 {
-        let (workflow_name, part) = queue.remove().unwrap();
-        let adjusted = workflows.get(workflow_name).unwrap().adjust(&part);
-        adjusted.0.iter().for_each(|it| {
-            queue.add(*it).unwrap();
-        });
-        accepted.extend(adjusted.1);
-    }
+        // ratings are optional, only the workflows block is needed for the second part
+        let parts: Vec<Part<u32>> = match blocks.get(1) {
+            Some(block) => block.lines()
+                .map(|line| parser.parse::<Part<u32>>(line))
+                .collect::<Result<_, _>>()?,
+            None => Vec::new(),
+        };
 
-    let result: u64 = accepted.iter().fold(0, |acc, it| acc + it.score());
-    return result.to_string();
+        return Ok(System { workflows, parts });
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT1: &str = "px{a<2006:qkq,m>2090:A,rfg}
+const INPUT1: &str = "px{a<2006:qkq,m>2090:A,rfg}
 pv{a>1716:R,A}
 lnx{m>1548:A,A}
 rfg{s<537:gd,x>2440:R,A}
@@ -345,6 +330,67 @@ hdj{m>838:A,pv}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}";
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = System;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT1, INPUT1];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.parse::<System>(input);
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let workflows: &HashMap<String, Workflow> = &input.workflows;
+
+        let result = input.parts.iter().fold(0, |acc, part| {
+            let mut next_action: &Action = workflows["in"].run(part);
+            loop {
+                match next_action {
+                    Action::Accept => {
+                        return acc + part.score();
+                    }
+                    Action::Reject => break,
+                    Forward(next_workflow) => {
+                        next_action = workflows[next_workflow.as_str()].run(part);
+                    }
+                }
+            }
+            return acc;
+        });
+
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let workflows: &HashMap<String, Workflow> = &input.workflows;
+
+        let mut queue: Queue<(&str, Part<Interval<u32>>)> = Queue::new();
+        queue.add(("in", Part::create(1, 4000))).unwrap();
+
+        let mut accepted: HashSet<Part<Interval<u32>>> = HashSet::new();
+
+        while queue.size() > 0 {
+            let (workflow_name, part) = queue.remove().unwrap();
+            let adjusted = workflows[workflow_name].adjust(&part);
+            adjusted.0.iter().for_each(|it| {
+                queue.add(*it).unwrap();
+            });
+            accepted.extend(adjusted.1);
+        }
+
+        let result: u64 = accepted.iter().fold(0, |acc, it| acc + it.score());
+        return Ok(Answer::from(result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     const INPUT2: &str = "px{a<2006:qkq,m>2090:A,rfg}
 pv{a>1716:R,A}
 lnx{m>1548:A,A}
@@ -359,13 +405,28 @@ hdj{m>838:A,pv}";
 
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT1);
+        let result = Day19::solve_part1(INPUT1).unwrap();
         assert_eq!(result, "19114");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT2);
+        let result = Day19::solve_part2(INPUT2).unwrap();
         assert_eq!(result, "167409079868000");
+
+        let result = Day19::solve_part2(INPUT1).unwrap();
+        assert_eq!(result, "167409079868000");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err: ParseError = Day19::parse("px{a<2006:A,R}\nin{s=1351:px,A}").err().unwrap();
+        assert_eq!((err.line, err.column, err.snippet.as_str()), (2, 5, "in{s=1351:px,A}"));
+        assert_eq!(err.message, "expected '<' or '>'");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day19>();
+    }
+}
@@ -1,26 +1,18 @@
-use std::cmp::min;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, VecDeque};
 use std::str::FromStr;
 
-use anyhow::Error;
-use clap::Parser;
-
-use common::math::lcm_vec;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
+use common::error::SolveError;
+use common::math;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
 trait Module {
     fn current(&self) -> bool;
     fn handle(&mut self, from: &str, signal: bool) -> bool;
+    fn boxed_clone(&self) -> Box<dyn Module>;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Switch {
     state: bool,
 }
@@ -37,206 +29,216 @@ impl Module for Switch {
         self.state = !self.state;
         return true;
     }
+
+    fn boxed_clone(&self) -> Box<dyn Module> {
+        return Box::new(self.clone());
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Conjunction {
     state: HashMap<String, bool>,
 }
 
 impl Module for Conjunction {
     fn current(&self) -> bool {
-        return !self.state.iter().fold(true, |acc, it| acc && *it.1);
+        return !self.state.values().all(|it| *it);
     }
 
     fn handle(&mut self, from: &str, signal: bool) -> bool {
         self.state.insert(from.to_string(), signal);
         return true;
     }
+
+    fn boxed_clone(&self) -> Box<dyn Module> {
+        return Box::new(self.clone());
+    }
 }
 
-#[derive(Debug)]
-struct Broadcast {}
+#[derive(Debug, Clone)]
+struct Broadcast {
+    signal: bool,
+}
 
 impl Module for Broadcast {
     fn current(&self) -> bool {
-        return false;
+        return self.signal;
     }
 
-    fn handle(&mut self, _: &str, _: bool) -> bool {
-        return false;
+    fn handle(&mut self, _: &str, signal: bool) -> bool {
+        self.signal = signal;
+        return true;
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Module> {
+        return Box::new(self.clone());
     }
 }
 
-struct DesertMachine {
+/// Button presses to wait for the modules feeding `rx` to send two high pulses each.
+const MAX_PRESSES: i64 = 100_000;
+
+pub struct DesertMachine {
     modules: HashMap<String, (Box<dyn Module>, Vec<String>)>,
 }
 
+impl Clone for DesertMachine {
+    fn clone(&self) -> Self {
+        return DesertMachine {
+            modules: self.modules.iter()
+                .map(|(name, (module, connections))| (name.clone(), (module.boxed_clone(), connections.clone())))
+                .collect(),
+        };
+    }
+}
+
 impl FromStr for DesertMachine {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut kids: HashMap<&str, Vec<&str>> = HashMap::new();
+        let parser: Parser = Parser::create(input);
         let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
 
-        input.split('\n').for_each(|line| {
-            let parts: Vec<&str> = line.split(" -> ").collect();
-            let parent: &str = parts.first().unwrap().trim_matches(|it| it == '%' || it == '&');
-            let next_kids: Vec<&str> = parts.last().unwrap().split(", ").collect();
-            next_kids.iter().for_each(|kid| {
-                if parents.contains_key(kid) {
-                    parents.get_mut(kid).unwrap().push(parent);
-                } else {
-                    parents.insert(kid, vec![parent]);
-                }
-            });
-            kids.insert(parent, next_kids);
-        });
+        let lines: Vec<(&str, &str, Vec<&str>)> = parser.lines().map(|line| {
+            let (module_part, next_kids) = parser.key_value(line, "->")?;
+            let module_name: &str = module_part.trim_matches(|it| it == '%' || it == '&');
+            let next_kids: Vec<&str> = next_kids.split(',').map(|it| it.trim()).collect();
+            next_kids.iter().for_each(|kid| parents.entry(kid).or_default().push(module_name));
+            return Ok((module_part, module_name, next_kids));
+        }).collect::<Result<_, ParseError>>()?;
+        if !lines.iter().any(|(module_part, _, _)| *module_part == "broadcaster") {
+            return Err(parser.error(input, "expected a 'broadcaster' module"));
+        }
 
-        let modules: HashMap<String, (Box<dyn Module>, Vec<String>)> = input.split('\n').map(|line| {
-            let module_part = line.split(" -> ").take(1).next().unwrap();
-            let module_name: &str = &module_part.trim_matches(|it| it == '%' || it == '&');
-            let module: Box<dyn Module> = match &module_part[..1] {
-                "%" => Box::new(Switch { state: false }),
-                "&" => Box::new(Conjunction {
-                    state: parents.get(module_name).unwrap().iter()
+        let modules: HashMap<String, (Box<dyn Module>, Vec<String>)> = lines.iter().map(|(module_part, module_name, next_kids)| {
+            let module: Box<dyn Module> = match module_part.chars().next() {
+                Some('%') => Box::new(Switch { state: false }),
+                Some('&') => Box::new(Conjunction {
+                    state: parents.get(module_name).into_iter().flatten()
                         .map(|it| (it.to_string(), false))
-                        .into_iter()
                         .collect()
                 }),
-                "b" => Box::new(Broadcast {}),
-                _ => panic!("Invalid module")
+                _ if *module_part == "broadcaster" => Box::new(Broadcast { signal: false }),
+                _ => return Err(parser.error(module_part, "expected '%', '&' or 'broadcaster'")),
             };
 
-            return (
-                module_name.to_string(),
-                (
-                    module,
-                    kids.get(module_name).unwrap().iter()
-                        .map(|it| it.to_string())
-                        .collect()
-                )
-            );
-        }).into_iter().collect();
+            return Ok((module_name.to_string(), (module, next_kids.iter().map(|it| it.to_string()).collect())));
+        }).collect::<Result<_, _>>()?;
 
         return Ok(DesertMachine { modules });
     }
 }
 
 impl DesertMachine {
-    fn apply(&mut self) -> (u32, u32) {
-        let mut total_low: u32 = 1;
-        let mut total_high: u32 = 0;
-
-        let mut active_modules: Vec<String> = vec!["broadcaster".to_string()];
-        while !active_modules.is_empty() {
-            let mut next_modules: Vec<String> = Vec::new();
-
-            let (low, high) = active_modules.iter().fold((0, 0), |acc, module_name| {
-                let (module, connections) = self.modules.get(module_name).unwrap();
+    /// Pushes the button once, `on_pulse` gets the sender and the signal of every pulse in the order they are sent.
+    fn press(&mut self, mut on_pulse: impl FnMut(&str, bool)) {
+        let mut pulses: VecDeque<(String, String, bool)> = VecDeque::from([("button".to_string(), "broadcaster".to_string(), false)]);
+        while let Some((from, to, signal)) = pulses.pop_front() {
+            on_pulse(&from, signal);
+            let Some((module, connections)) = self.modules.get_mut(&to) else {
+                continue;
+            };
+            if module.handle(&from, signal) {
                 let signal: bool = module.current();
-                return connections.clone().iter().fold(acc, |acc, next_name| {
-                    if self.modules.contains_key(next_name) {
-                        let (next_module, _) = self.modules.get_mut(next_name).unwrap();
-                        if next_module.handle(module_name.as_str(), signal) {
-                            next_modules.push(next_name.clone());
-                        }
-                    }
-                    return if signal { (acc.0, acc.1 + 1) } else { (acc.0 + 1, acc.1) };
-                });
-            });
+                pulses.extend(connections.iter().map(|next_name| (to.clone(), next_name.clone(), signal)));
+            }
+        }
+    }
 
-            total_low += low;
-            total_high += high;
+    fn apply(&mut self) -> (u32, u32) {
+        let mut total_low: u32 = 0;
+        let mut total_high: u32 = 0;
 
-            active_modules = next_modules;
-        }
+        self.press(|_, signal| if signal { total_high += 1 } else { total_low += 1 });
 
         return (total_low, total_high);
     }
 
     fn find_parents(&self, module_name: &String) -> Vec<String> {
         return self.modules.iter().filter_map(|(module, (_, modules))| {
-            return if modules.first().unwrap() == module_name { Some(module.clone()) } else { None };
+            return if modules.contains(module_name) { Some(module.clone()) } else { None };
         }).collect();
     }
 
-    fn calc_min_steps(&mut self, final_modules: &Vec<String>) -> Vec<u64> {
-        let mut idx: u64 = 0;
-
-        let mut min_steps: HashMap<&String, u64> = final_modules.iter()
-            .map(|module_name| (module_name, u64::MAX))
-            .into_iter()
+    /// Button presses of the first high pulse of every module and presses between it and the next high pulse.
+    fn find_cycles(&mut self, final_modules: &[String]) -> Result<Vec<(i64, i64)>, SolveError> {
+        let mut high_pulses: HashMap<&str, Vec<i64>> = final_modules.iter()
+            .map(|module_name| (module_name.as_str(), Vec::new()))
             .collect();
 
-        loop {
-            let mut active_modules: Vec<String> = vec!["broadcaster".to_string()];
-            while !active_modules.is_empty() {
-                let mut next_modules: Vec<String> = Vec::new();
-
-                for module_name in active_modules {
-                    let (module, connections) = self.modules.get(module_name.as_str()).unwrap();
-                    let signal: bool = module.current();
-                    for next_name in connections.clone() {
-                        if self.modules.contains_key(next_name.as_str()) {
-                            let (next_module, _) = self.modules.get_mut(&next_name).unwrap();
-                            if next_module.handle(module_name.as_str(), signal) {
-                                next_modules.push(next_name.clone());
-                                if signal && min_steps.contains_key(&module_name) {
-                                    if let Some(value) = min_steps.get_mut(&module_name) {
-                                        *value = min(*value, idx + 1)
-                                    }
-                                    if min_steps.iter().all(|it| *it.1 != u64::MAX) {
-                                        return min_steps.iter().map(|it| *it.1).collect();
-                                    }
-                                }
-                            }
-                        }
-                    };
-                };
-                active_modules = next_modules;
+        for idx in 1..=MAX_PRESSES {
+            self.press(|module_name, signal| {
+                if let Some(presses) = high_pulses.get_mut(module_name) {
+                    if signal && presses.last() != Some(&idx) {
+                        presses.push(idx);
+                    }
+                }
+            });
+            if high_pulses.values().all(|it| it.len() >= 2) {
+                return Ok(final_modules.iter()
+                    .map(|it| (high_pulses[it.as_str()][0], high_pulses[it.as_str()][1] - high_pulses[it.as_str()][0]))
+                    .collect());
             }
-            idx += 1;
         }
+
+        return Err(SolveError::NoSolution(format!("modules feeding 'rx' do not repeat within {} presses", MAX_PRESSES)));
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let mut machine: DesertMachine = input.parse::<DesertMachine>().unwrap();
+const INPUT1: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
 
-    let (low, high) = (0..1000).fold((0, 0), |acc, _| {
-        let (low, high) = machine.apply();
-        return (acc.0 + low, acc.1 + high);
-    });
+pub struct Day20;
 
-    let result: u64 = low as u64 * high as u64;
+impl Solution for Day20 {
+    type Input = DesertMachine;
+    type Params = ();
 
-    return result.to_string();
-}
+    const EXAMPLES: &'static [&'static str] = &[INPUT1];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.parse::<DesertMachine>(input);
+    }
 
-pub fn solve_part2(input: &str) -> String {
-    let mut machine: DesertMachine = input.parse::<DesertMachine>().unwrap();
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let mut machine: DesertMachine = input.clone();
 
-    let rx_parent: String = machine.find_parents(&"rx".to_string()).first()
-        .expect("Cannot find parent module for 'rx'")
-        .clone();
+        let (low, high) = (0..1000).fold((0, 0), |acc, _| {
+            let (low, high) = machine.apply();
+            return (acc.0 + low, acc.1 + high);
+        });
+
+        let result: u64 = low as u64 * high as u64;
+
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let mut machine: DesertMachine = input.clone();
 
-    let min_steps: Vec<u64> = machine.calc_min_steps(&machine.find_parents(&rx_parent));
-    let result = lcm_vec(&min_steps).expect("Failed to calculate LCM");
+        let [rx_parent]: [String; 1] = machine.find_parents(&"rx".to_string()).try_into().map_err(|parents: Vec<String>| {
+            return SolveError::NoSolution(format!("expected a single module sending pulses to 'rx', found {}", parents.len()));
+        })?;
 
-    return result.to_string();
+        let feeders: Vec<String> = machine.find_parents(&rx_parent);
+        if feeders.is_empty() {
+            return Err(SolveError::NoSolution(format!("no module sends pulses to '{}'", rx_parent)));
+        }
+        let cycles: Vec<(i64, i64)> = machine.find_cycles(&feeders)?;
+        let result: i64 = math::first_common(&cycles).ok_or_else(|| SolveError::NoSolution("modules feeding 'rx' never send high pulses together".to_string()))?;
+
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT1: &str = "broadcaster -> a, b, c
-%a -> b
-%b -> c
-%c -> inv
-&inv -> a";
-
     const INPUT2: &str = "broadcaster -> a
 %a -> inv, con
 &inv -> b
@@ -245,10 +247,36 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT1);
+        let result = Day20::solve_part1(INPUT1).unwrap();
         assert_eq!(result, "32000000");
 
-        let result = solve_part1(INPUT2);
+        let result = Day20::solve_part1(INPUT2).unwrap();
         assert_eq!(result, "11687500");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_error() {
+        assert!(Day20::parse("%a -> b\n%b -> a").is_err());
+        assert!(Day20::parse("broadcaster -> a\n*a -> b").is_err());
+    }
+
+    #[test]
+    fn test_part2_no_solution() {
+        let result = Day20::solve_part2("broadcaster -> x\n&x -> y\n&y -> z\n&z -> rx");
+        assert!(matches!(result, Err(SolveError::NoSolution(_))));
+    }
+
+    #[test]
+    fn test_part2_without_feeders() {
+        let result = Day20::solve_part2("broadcaster -> a\n%a -> a\n&y -> rx");
+        assert!(matches!(result, Err(SolveError::NoSolution(_))));
+
+        let result = Day20::solve_part2("broadcaster -> a, b\n&a -> rx\n&b -> rx");
+        assert!(matches!(result, Err(SolveError::NoSolution(_))));
+    }
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day20>();
+    }
+}
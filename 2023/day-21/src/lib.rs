@@ -1,45 +1,15 @@
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
-use std::path::PathBuf;
 use std::str::FromStr;
 
-use anyhow::Error;
-use clap::Parser;
-use ndarray::Array2;
-
-use crate::Direction::{DOWN, LEFT, RIGHT, UP};
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
-#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
-struct Position {
-    x: i32,
-    y: i32,
-}
-
-#[derive(Debug)]
-enum Direction {
-    UP,
-    RIGHT,
-    DOWN,
-    LEFT,
-}
-
-impl Direction {
-    fn next(&self, pos: &Position) -> Position {
-        return match self {
-            UP => Position { x: pos.x - 1, y: pos.y },
-            RIGHT => Position { x: pos.x, y: pos.y + 1 },
-            DOWN => Position { x: pos.x + 1, y: pos.y },
-            LEFT => Position { x: pos.x, y: pos.y - 1 }
-        };
-    }
-}
+use common::error::SolveError;
+use common::geom::Position;
+use common::grid::Grid;
+use common::linalg;
+use common::parse::{ParseError, Parser};
+use common::poly::Polynomial;
+use common::{Answer, Solution};
+use num_rational::BigRational;
 
 #[derive(Clone, Eq, PartialEq)]
 enum Tile {
@@ -58,112 +28,62 @@ impl Debug for Tile {
     }
 }
 
-struct Garden {
-    data: Array2<Tile>,
+pub struct Garden {
+    data: Grid<Tile>,
 }
 
 impl FromStr for Garden {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let data: Vec<Vec<Tile>> = input.split('\n')
-            .map(|line| line.chars()
-                .map(|it| match it {
-                    '.' => Tile::GardenPlot,
-                    '#' => Tile::Rock,
-                    'S' => Tile::Start,
-                    tile => panic!("Invalid tile: {}", tile)
-                }).collect()
-            ).collect();
-
-        return Ok(Garden {
-            data: Array2::from_shape_vec(
-                (data.len(), data.first().unwrap().len()),
-                data.into_iter().flatten().collect(),
-            ).unwrap()
-        });
+        let parser: Parser = Parser::create(input);
+        let data: Grid<Tile> = parser.grid(input, |it| match it {
+            '.' => Some(Tile::GardenPlot),
+            '#' => Some(Tile::Rock),
+            'S' => Some(Tile::Start),
+            _ => None,
+        })?;
+        if data.position_of(&Tile::Start).is_none() {
+            return Err(parser.error(input, "garden has no starting position 'S'"));
+        }
+
+        return Ok(Garden { data });
     }
 }
 
 impl Garden {
+    /// Tile of the garden repeated infinitely in every direction.
     fn get_tile(&self, position: &Position) -> &Tile {
-        let shape: &[usize] = self.data.shape();
-        let x = (position.x % shape[0] as i32 + shape[0] as i32) % shape[0] as i32;
-        let y = (position.y % shape[1] as i32 + shape[1] as i32) % shape[1] as i32;
-        return &self.data[[x as usize, y as usize]];
+        return self.data.get_wrapping(position.row as isize, position.col as isize);
     }
 
     fn find_start(&self) -> Position {
-        return self.data.indexed_iter().find_map(|((x, y), tile)| {
-            if *tile == Tile::Start { Some(Position { x: x as i32, y: y as i32 }) } else { None }
-        }).unwrap();
+        return Position::from(self.data.position_of(&Tile::Start).unwrap());
     }
 
     fn travel(&self, prev_plots: &HashSet<Position>) -> HashSet<Position> {
-        let directions: [Direction; 4] = [DOWN, UP, LEFT, RIGHT];
-
         let mut next_plots: HashSet<Position> = HashSet::new();
         for plot in prev_plots {
-            next_plots.extend(
-                directions.iter().filter_map(|it| {
-                    let next_position: Position = it.next(&plot);
-                    return if *self.get_tile(&next_position) != Tile::Rock { Some(next_position) } else { None };
-                })
-            )
+            next_plots.extend(plot.neighbours4().filter(|it| *self.get_tile(it) != Tile::Rock))
         }
 
         return next_plots;
     }
 }
 
-pub fn solve_part1(input: &str, steps_count: u32) -> String {
-    let garden: Garden = input.parse::<Garden>().unwrap();
-
-    let plots: HashSet<Position> = (0..steps_count)
-        .fold(HashSet::from([garden.find_start()]), |prev, _| garden.travel(&prev));
-
-    let result: usize = plots.len();
-    return result.to_string();
+/// Number of steps the elf walks in each part of the puzzle.
+pub struct Steps {
+    pub part1: u32,
+    pub part2: usize,
 }
 
-// took a while to spot a cycle, but looks like number of reachable plots is increased on some delta each "GRID SIZE" steps
-// so solution can be split into 2 parts
-// 1) find delta
-// 2) calculate number of plots for 26501365 steps
-pub fn solve_part2(input: &str, steps_count: usize) -> String {
-    let garden: Garden = input.parse::<Garden>().unwrap();
-
-    let shape: &[usize] = garden.data.shape();
-    let center : usize = shape[0] / 2;
-    let cycles_count: usize = 2;
-
-    let (mut plots_counts, plots) = (0..center + shape[0] * cycles_count).fold(
-        (Vec::new(), HashSet::from([garden.find_start()])),
-        |(mut counts, prev), step| {
-            if step == center || (step > center && (step - center) % shape[0] == 0) {
-                counts.push(prev.len());
-            }
-            return (counts, garden.travel(&prev));
-        },
-    );
-
-    plots_counts.push(plots.len());
-    plots_counts.reverse();
-
-    let delta: Vec<usize> = plots_counts.windows(2).map(|it| it[0] - it[1]).collect();
-    let (result, _) = (0..(steps_count - center) / shape[0]).fold(
-        (*plots_counts.last().unwrap(), *delta.last().unwrap()),
-        |(total, step_delta), _| (total + step_delta, step_delta + delta[0] - delta[1]),
-    );
-
-    return result.to_string();
+impl Default for Steps {
+    fn default() -> Steps {
+        return Steps { part1: 64, part2: 26501365 };
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT1: &str = "...........
+const INPUT1: &str = "...........
 .....###.#.
 .###.##..#.
 ..#.#...#..
@@ -175,10 +95,81 @@ mod tests {
 .##..##.##.
 ...........";
 
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Garden;
+    type Params = Steps;
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT1];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.parse::<Garden>(input);
+    }
+
+    fn example_params() -> Self::Params {
+        return Steps { part1: 6, ..Steps::default() };
+    }
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<Answer, SolveError> {
+        let plots: HashSet<Position> = (0..params.part1)
+            .fold(HashSet::from([input.find_start()]), |prev, _| input.travel(&prev));
+
+        let result: usize = plots.len();
+        return Ok(Answer::from(result));
+    }
+
+    // the garden repeats and the rows and column of the start are free of rocks, so the number of reachable plots
+    // after `center + k * size` steps grows as a quadratic polynomial of `k`
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<Answer, SolveError> {
+        let size: usize = input.data.height();
+        if input.data.width() != size {
+            return Err(SolveError::NoSolution(format!("garden is {}x{}, the extrapolation needs a square one", input.data.width(), size)));
+        }
+        let center: usize = size / 2;
+        if params.part2 < center || !(params.part2 - center).is_multiple_of(size) {
+            return Err(SolveError::InvalidParameter(format!("steps must be {} plus a multiple of {}", center, size)));
+        }
+
+        let (points, _) = (0..=center + size * 2).fold(
+            (Vec::new(), HashSet::from([input.find_start()])),
+            |(mut points, prev), step| {
+                if step >= center && (step - center).is_multiple_of(size) {
+                    points.push((points.len() as i64, prev.len() as i64));
+                }
+                return (points, input.travel(&prev));
+            },
+        );
+
+        let polynomial: Polynomial = Polynomial::fit_integers(&points);
+        let result: BigRational = polynomial.eval(&linalg::rational(((params.part2 - center) / size) as i64));
+        if !result.is_integer() {
+            return Err(SolveError::NoSolution(format!("plots count is not an integer: {}", result)));
+        }
+        return Ok(Answer::from(result.to_integer()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT1, 6);
+        let result = Day21::part1(&Day21::parse(INPUT1).unwrap(), &Steps { part1: 6, ..Steps::default() }).unwrap();
         assert_eq!(result, "16");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_part2_not_square() {
+        let input: Garden = Day21::parse("...\n.S.\n...\n...").unwrap();
+        let result = Day21::part2(&input, &Steps::default());
+        assert!(matches!(result, Err(SolveError::NoSolution(_))));
+    }
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day21>();
+    }
+}
@@ -1,22 +1,15 @@
 use std::cmp::{max, min};
 use std::collections::HashSet;
-use std::path::PathBuf;
 use std::str::FromStr;
 
-use anyhow::Error;
-use clap::Parser;
+use common::error::SolveError;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 use itertools::Itertools;
 use ndarray::Array2;
 use petgraph::Direction;
 use petgraph::prelude::DiGraphMap;
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
 #[derive(Debug, Clone)]
 struct Point {
     x: usize,
@@ -25,31 +18,33 @@ struct Point {
 }
 
 impl FromStr for Point {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let coords: Vec<usize> = input.split(",").map(|it| it.parse::<usize>().unwrap()).collect();
-        return Ok(Point { x: coords[0], y: coords[1], z: coords[2] });
+        let parser: Parser = Parser::create(input);
+        let [x, y, z] = parser.fields(input, ",")?;
+        return Ok(Point { x: parser.value(x)?, y: parser.value(y)?, z: parser.value(z)? });
     }
 }
 
 #[derive(Debug)]
-struct Snapshot {
+pub struct Snapshot {
     bricks: Vec<(Point, Point)>,
     bounds: (usize, usize),
 }
 
 impl FromStr for Snapshot {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut bricks: Vec<(Point, Point)> = input.split('\n')
+        let parser: Parser = Parser::create(input);
+        let mut bricks: Vec<(Point, Point)> = parser.lines()
             .map(|line| {
-                let parts: Vec<&str> = line.split("~").collect();
-                return (parts[0].parse::<Point>().unwrap(), parts[1].parse::<Point>().unwrap());
-            }).collect();
+                let [start, end] = parser.fields(line, "~")?;
+                return Ok((parser.parse::<Point>(start)?, parser.parse::<Point>(end)?));
+            }).collect::<Result<_, ParseError>>()?;
 
-        bricks.sort_by(|a, b| min(a.0.z, a.1.z).cmp(&min(b.0.z, b.1.z)));
+        bricks.sort_by_key(|brick| min(brick.0.z, brick.1.z));
 
         let bounds: (usize, usize) = bricks.iter().fold((0, 0), |acc, brick| {
             return (
@@ -73,7 +68,7 @@ impl Snapshot {
             let mut z_offset: usize = 0;
 
             // calculate dependent bricks
-            (brick.0.x..=brick.1.x).into_iter().cartesian_product((brick.0.y..=brick.1.y).into_iter()).for_each(|(x, y)| {
+            (brick.0.x..=brick.1.x).cartesian_product(brick.0.y..=brick.1.y).for_each(|(x, y)| {
                 if z_offsets[[x, y]] > z_offset {
                     z_offset = z_offsets[[x, y]];
 
@@ -91,7 +86,7 @@ impl Snapshot {
             });
 
             // update offsets and indexes
-            (brick.0.x..=brick.1.x).into_iter().cartesian_product((brick.0.y..=brick.1.y).into_iter()).for_each(|(x, y)| {
+            (brick.0.x..=brick.1.x).cartesian_product(brick.0.y..=brick.1.y).for_each(|(x, y)| {
                 z_offsets[[x, y]] = z_offset + (brick.1.z - brick.0.z + 1);
                 z_indexes[[x, y]] = idx;
             })
@@ -101,73 +96,90 @@ impl Snapshot {
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let snapshot: Snapshot = input.parse::<Snapshot>().unwrap();
-    let graph = snapshot.to_graph();
+const INPUT: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+pub struct Day22;
 
-    let result = (0..snapshot.bricks.len()).fold(0, |acc, idx| {
-        let can_be_disintegrated: bool = graph.neighbors_directed(idx, Direction::Incoming).all(|candidate_idx| {
-            return graph.neighbors_directed(candidate_idx, Direction::Outgoing).count() > 1;
+impl Solution for Day22 {
+    type Input = Snapshot;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.parse::<Snapshot>(input);
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let graph = input.to_graph();
+
+        let result = (0..input.bricks.len()).fold(0, |acc, idx| {
+            let can_be_disintegrated: bool = graph.neighbors_directed(idx, Direction::Incoming).all(|candidate_idx| {
+                return graph.neighbors_directed(candidate_idx, Direction::Outgoing).count() > 1;
+            });
+            return if can_be_disintegrated { acc + 1 } else { acc };
         });
-        return if can_be_disintegrated { acc + 1 } else { acc };
-    });
 
-    return result.to_string();
-}
+        return Ok(Answer::from(result));
+    }
 
-pub fn solve_part2(input: &str) -> String {
-    let snapshot: Snapshot = input.parse::<Snapshot>().unwrap();
-    let graph = snapshot.to_graph();
-
-    let result = (0..snapshot.bricks.len()).fold(0, |acc, idx| {
-        let mut fallen: HashSet<usize> = HashSet::from([idx]);
-        let mut queue: Vec<usize> = Vec::from([idx]);
-        loop {
-            let mut next_bricks: Vec<usize> = Vec::new();
-
-            for brick_idx in queue.iter() {
-                graph.neighbors_directed(*brick_idx, Direction::Incoming).for_each(|candidate_idx| {
-                    if graph.neighbors_directed(candidate_idx, Direction::Outgoing).all(|it| fallen.contains(&it)) {
-                        next_bricks.push(candidate_idx);
-                        fallen.insert(candidate_idx);
-                    }
-                })
-            }
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let graph = input.to_graph();
+
+        let result = (0..input.bricks.len()).fold(0, |acc, idx| {
+            let mut fallen: HashSet<usize> = HashSet::from([idx]);
+            let mut queue: Vec<usize> = Vec::from([idx]);
+            loop {
+                let mut next_bricks: Vec<usize> = Vec::new();
+
+                for brick_idx in queue.iter() {
+                    graph.neighbors_directed(*brick_idx, Direction::Incoming).for_each(|candidate_idx| {
+                        if graph.neighbors_directed(candidate_idx, Direction::Outgoing).all(|it| fallen.contains(&it)) {
+                            next_bricks.push(candidate_idx);
+                            fallen.insert(candidate_idx);
+                        }
+                    })
+                }
 
-            if next_bricks.is_empty() {
-                break;
-            }
+                if next_bricks.is_empty() {
+                    break;
+                }
 
-            queue = next_bricks;
-        }
+                queue = next_bricks;
+            }
 
-        return acc + fallen.len() - 1;
-    });
+            return acc + fallen.len() - 1;
+        });
 
-    return result.to_string();
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "1,0,1~1,2,1
-0,0,2~2,0,2
-0,2,3~2,2,3
-0,0,4~0,2,4
-2,0,5~2,2,5
-0,1,6~2,1,6
-1,1,8~1,1,9";
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day22::solve_part1(INPUT).unwrap();
         assert_eq!(result, "5");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day22::solve_part2(INPUT).unwrap();
         assert_eq!(result, "7");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day22>();
+    }
+}
@@ -1,61 +1,19 @@
-use std::cmp::max;
 use std::collections::{HashSet, VecDeque};
 use std::fmt::{Debug, Formatter};
-use std::path::PathBuf;
 
-use clap::Parser;
+use common::error::SolveError;
+use common::geom::{Direction, Position};
+use common::grid::Grid;
+use common::search;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 use linked_hash_set::LinkedHashSet;
-use ndarray::Array2;
 use petgraph::Outgoing;
 use petgraph::prelude::DiGraphMap;
 
-use crate::Direction::{DOWN, LEFT, RIGHT, UP};
 use crate::TrailItem::{Forest, Path, Slop};
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
-#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Ord, PartialOrd)]
-struct Position {
-    x: i32,
-    y: i32,
-}
-
-#[derive(Eq, PartialEq)]
-enum Direction {
-    UP,
-    RIGHT,
-    DOWN,
-    LEFT,
-}
-
-impl Debug for Direction {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            UP => write!(f, "^"),
-            RIGHT => write!(f, ">"),
-            DOWN => write!(f, "v"),
-            LEFT => write!(f, "<")
-        }
-    }
-}
-
-impl Direction {
-    fn next(&self, pos: &Position) -> Position {
-        return match self {
-            UP => Position { x: pos.x - 1, y: pos.y },
-            RIGHT => Position { x: pos.x, y: pos.y + 1 },
-            DOWN => Position { x: pos.x + 1, y: pos.y },
-            LEFT => Position { x: pos.x, y: pos.y - 1 }
-        };
-    }
-}
-
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 enum TrailItem {
     Path,
     Forest,
@@ -67,7 +25,10 @@ impl Debug for TrailItem {
         match self {
             Path => write!(f, "."),
             Forest => write!(f, "#"),
-            Slop(direction) => write!(f, "{:?}", direction),
+            Slop(Direction::Up) => write!(f, "^"),
+            Slop(Direction::Right) => write!(f, ">"),
+            Slop(Direction::Down) => write!(f, "v"),
+            Slop(Direction::Left) => write!(f, "<"),
         }
     }
 }
@@ -75,68 +36,64 @@ impl Debug for TrailItem {
 impl TrailItem {
     fn next(&self, pos: &Position) -> Vec<Position> {
         return match self {
-            Path => [UP, LEFT, DOWN, RIGHT].iter().map(|it| it.next(pos)).collect(),
+            Path => [Direction::Up, Direction::Left, Direction::Down, Direction::Right].iter().map(|it| pos.step(*it)).collect(),
             Forest => Vec::new(),
-            Slop(direction) => vec![direction.next(pos)]
+            Slop(direction) => vec![pos.step(*direction)]
         };
     }
 }
 
 #[derive(Debug)]
-struct Trails {
-    data: Array2<TrailItem>,
-    shape: (i32, i32),
+pub struct Trails {
+    data: Grid<TrailItem>,
 }
 
 impl Trails {
-    fn create(input: &str, skip_slopes: bool) -> Trails {
-        let data: Vec<Vec<TrailItem>> = input.split('\n')
-            .map(|line| line.chars()
-                .map(|it| match it {
-                    '.' => Path,
-                    '#' => Forest,
-                    '>' => if skip_slopes { Path } else { Slop(RIGHT) },
-                    '<' => if skip_slopes { Path } else { Slop(LEFT) },
-                    '^' => if skip_slopes { Path } else { Slop(UP) },
-                    'v' => if skip_slopes { Path } else { Slop(DOWN) },
-                    invalid => panic!("Invalid item: {}", invalid)
-                }).collect()
-            ).collect();
-
-        let shape: (i32, i32) = (data.len() as i32, data.first().unwrap().len() as i32);
+    fn create(input: &str) -> Result<Trails, ParseError> {
+        let parser: Parser = Parser::create(input);
+        let data: Grid<TrailItem> = parser.grid(input, |it| match it {
+            '.' => Some(Path),
+            '#' => Some(Forest),
+            '>' => Some(Slop(Direction::Right)),
+            '<' => Some(Slop(Direction::Left)),
+            '^' => Some(Slop(Direction::Up)),
+            'v' => Some(Slop(Direction::Down)),
+            _ => None,
+        })?;
+
+        return Ok(Trails { data });
+    }
 
+    fn without_slopes(&self) -> Trails {
         return Trails {
-            data: Array2::from_shape_vec(
-                (data.len(), data.first().unwrap().len()),
-                data.into_iter().flatten().collect(),
-            ).unwrap(),
-            shape,
+            data: self.data.map(|it| if let Slop(_) = it { Path } else { *it }),
         };
     }
 
+    fn finish(&self) -> Position {
+        return Position::create(self.data.height() as i64 - 1, self.data.width() as i64 - 2);
+    }
+
     fn is_path(&self, pos: &Position) -> bool {
-        return pos.x >= 0 && pos.x < self.shape.0 && pos.y >= 0 && pos.y < self.shape.1 && self.data[[pos.x as usize, pos.y as usize]] != Forest;
+        return self.data.at(*pos).is_some_and(|it| *it != Forest);
     }
 
     fn next(&self, pos: &Position) -> Vec<Position> {
-        return self.data[[pos.x as usize, pos.y as usize]].next(pos).iter().filter_map(|pos| {
+        return self.data[*pos].next(pos).iter().filter_map(|pos| {
             return if self.is_path(pos) { Some(*pos) } else { None };
         }).collect::<Vec<Position>>();
     }
 
     fn to_graph(&self) -> DiGraphMap<Position, usize> {
-        let start: Position = Position { x: 0, y: 1 };
-        let finish: Position = Position { x: self.shape.0 - 1, y: self.shape.1 - 2 };
+        let start: Position = Position::create(0, 1);
+        let finish: Position = self.finish();
 
         let mut split_points: LinkedHashSet<Position> = LinkedHashSet::new();
         split_points.insert(start);
         split_points.insert(finish);
 
         split_points.extend(
-            self.data.indexed_iter().filter_map(|((x, y), item)| {
-                let pos: Position = Position { x: x as i32, y: y as i32 };
-                return if *item != Forest && self.next(&pos).len() >= 3 { Some(pos) } else { None };
-            })
+            self.data.positions().filter(|pos| self.data[*pos] != Forest && self.next(pos).len() >= 3)
         );
 
         let mut graph: DiGraphMap<Position, usize> = DiGraphMap::new();
@@ -151,7 +108,7 @@ impl Trails {
                 }
 
                 let (pos, distance) = queue.pop_back().unwrap();
-                let item = &self.data[[pos.x as usize, pos.y as usize]];
+                let item = &self.data[pos];
 
                 if distance > 0 && split_points.contains(&pos) {
                     graph.add_edge(*point, pos, distance);
@@ -170,47 +127,20 @@ impl Trails {
         return graph;
     }
 
-    fn find_longest(&self) -> usize {
+    fn find_longest(&self) -> Result<usize, SolveError> {
         let graph: DiGraphMap<Position, usize> = self.to_graph();
-        let start: Position = Position { x: 0, y: 1 };
-
-        return self.find_longest_dfs(&graph, &start, &mut HashSet::new(), 0);
-    }
-
-    fn find_longest_dfs(&self, graph: &DiGraphMap<Position, usize>, from: &Position, visited: &mut HashSet<Position>, distance: usize) -> usize {
-        if from.x == self.shape.0 - 1 && from.y == self.shape.1 - 2 {
-            return distance;
-        }
-
-        let mut longest: usize = 0;
-        graph.edges_directed(*from, Outgoing).for_each(|(_, next, next_distance)| {
-            if visited.insert(next) {
-                longest = max(longest, self.find_longest_dfs(graph, &next, visited, distance + next_distance));
-                visited.remove(&next);
-            }
-        });
-
-        return longest;
+        let finish: Position = self.finish();
+
+        return search::longest_path(
+            Position::create(0, 1),
+            |from| graph.edges_directed(*from, Outgoing).map(|(_, next, distance)| (next, *distance)),
+            |pos| *pos == finish,
+            None,
+        ).map(|path| path.cost).ok_or_else(|| SolveError::NoSolution("no path reaches the exit".to_string()));
     }
 }
 
-pub fn solve_part1(input: &str) -> String {
-    let trails: Trails = Trails::create(input, false);
-    let result: usize = trails.find_longest();
-    return result.to_string();
-}
-
-pub fn solve_part2(input: &str) -> String {
-    let trails: Trails = Trails::create(input, true);
-    let result: usize = trails.find_longest();
-    return result.to_string();
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = "#.#####################
+const INPUT: &str = "#.#####################
 #.......#########...###
 #######.#########.#.###
 ###.....#.>.>.###.#.###
@@ -234,16 +164,54 @@ mod tests {
 #.....###...###...#...#
 #####################.#";
 
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Trails;
+    type Params = ();
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        return Trails::create(input);
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: usize = input.find_longest()?;
+        return Ok(Answer::from(result));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let result: usize = input.without_slopes().find_longest()?;
+        return Ok(Answer::from(result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day23::solve_part1(INPUT).unwrap();
         assert_eq!(result, "94");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day23::solve_part2(INPUT).unwrap();
         assert_eq!(result, "154");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_no_path() {
+        let input: &str = "#.###\n#.###\n#####\n###.#";
+        assert!(matches!(Day23::solve_part1(input), Err(SolveError::NoSolution(_))));
+        assert!(matches!(Day23::solve_part2(input), Err(SolveError::NoSolution(_))));
+    }
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day23>();
+    }
+}
@@ -1,9 +1,7 @@
-use std::fs;
-use clap::Parser;
-use aoc2023_day_24::{Cli, solve_part1};
+use std::process::ExitCode;
 
-fn main() {
-    let args: Cli = Cli::parse();
-    let input: String = fs::read_to_string(args.data_dir.join("input.txt")).unwrap();
-    println!("{}", solve_part1(&input, 200000000000000f64, 400000000000000f64));
+use aoc2023_day_24::Day24;
+
+fn main() -> ExitCode {
+    return common::cli::run::<Day24>(2023, 24, 1);
 }
\ No newline at end of file
@@ -1,149 +1,153 @@
-use std::path::PathBuf;
 use std::str::FromStr;
 
-use anyhow::Error;
-use clap::Parser;
-use generator::{Generator, Gn};
-use itertools::Itertools;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
+use common::error::SolveError;
+use common::geom::Point3;
+use common::linalg::{self, rational};
+use common::par;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, Zero};
 
 #[derive(Debug)]
-struct Hailstone {
-    position: (i64, i64, i64),
-    velocity: (i64, i64, i64),
+pub struct Hailstone {
+    position: Point3,
+    velocity: Point3,
 }
 
 impl FromStr for Hailstone {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let pos_vel: Vec<i64> = input.replace(" @ ", ",")
-            .split(",")
-            .map(|it| it.trim().parse::<i64>().unwrap())
-            .collect();
-        return Ok(Hailstone {
-            position: (pos_vel[0], pos_vel[1], pos_vel[2]),
-            velocity: (pos_vel[3], pos_vel[4], pos_vel[5]),
-        });
+        let parser: Parser = Parser::create(input);
+        let point = |part: &str| -> Result<Point3, ParseError> {
+            let [x, y, z] = parser.fields(part, ",")?;
+            return Ok(Point3::create(parser.value(x)?, parser.value(y)?, parser.value(z)?));
+        };
+        let (position, velocity) = parser.key_value(input, "@")?;
+        return Ok(Hailstone { position: point(position)?, velocity: point(velocity)? });
     }
 }
 
 impl Hailstone {
-    fn bias(&self) -> (f64, f64) {
-        let bias = self.velocity.1 as f64 / self.velocity.0 as f64;
-        return (self.position.1 as f64 - (self.position.0 as f64 * bias), bias);
-    }
-
-    fn intersect(&self, other: &Hailstone) -> Option<(f64, f64)> {
-        let bias1: (f64, f64) = self.bias();
-        let bias2: (f64, f64) = other.bias();
-
-        let x: f64 = (bias2.0 - bias1.0) / (bias1.1 - bias2.1);
-        let y: f64 = bias1.1 * x + bias1.0;
+    /// Future intersection of the paths on the X and Y axes.
+    fn intersect(&self, other: &Hailstone) -> Option<(BigRational, BigRational)> {
+        let (time, other_time) = linalg::intersect_lines_2d(
+            (self.position.x, self.position.y),
+            (self.velocity.x, self.velocity.y),
+            (other.position.x, other.position.y),
+            (other.velocity.x, other.velocity.y),
+        )?;
+
+        if time.is_negative() || other_time.is_negative() {
+            return None;
+        }
 
-        return if self.is_before(x, y) || other.is_before(x, y) { None } else { Some((x, y)) };
+        return Some((
+            rational(self.position.x) + &time * rational(self.velocity.x),
+            rational(self.position.y) + &time * rational(self.velocity.y),
+        ));
     }
+}
 
-    fn is_before(&self, x: f64, y: f64) -> bool {
-        return ((x - self.position.0 as f64) / self.velocity.0 as f64) < 0f64
-            || ((y - self.position.1 as f64) / self.velocity.1 as f64) < 0f64;
-    }
+/// Equations of the rock `(x, y, z, vx, vy, vz)` hitting both hailstones.
+///
+/// The rock hits a hailstone when `(P - p) x (V - v) = 0`. The `P x V` term is shared by all hailstones, so the
+/// difference of the equations of two hailstones is linear: `P x (v2 - v1) + (p2 - p1) x V = p2 x v2 - p1 x v1`.
+fn rock_equations(first: &Hailstone, second: &Hailstone) -> Vec<(Vec<BigRational>, BigRational)> {
+    let exact = |it: Point3| [rational(it.x), rational(it.y), rational(it.z)];
+    let cross = |a: &[BigRational; 3], b: &[BigRational; 3]| [
+        &a[1] * &b[2] - &a[2] * &b[1],
+        &a[2] * &b[0] - &a[0] * &b[2],
+        &a[0] * &b[1] - &a[1] * &b[0],
+    ];
+    let minus = |a: Point3, b: Point3| -> [BigRational; 3] {
+        let (a, b) = (exact(a), exact(b));
+        return [&a[0] - &b[0], &a[1] - &b[1], &a[2] - &b[2]];
+    };
+    let [dx, dy, dz] = minus(second.velocity, first.velocity);
+    let [ex, ey, ez] = minus(second.position, first.position);
+    let [sx, sy, sz] = cross(&exact(second.position), &exact(second.velocity));
+    let [fx, fy, fz] = cross(&exact(first.position), &exact(first.velocity));
+    let zero: BigRational = BigRational::zero();
+
+    return vec![
+        (vec![zero.clone(), dz.clone(), -dy.clone(), zero.clone(), -ez.clone(), ey.clone()], sx - fx),
+        (vec![-dz, zero.clone(), dx.clone(), ez, zero.clone(), -ex.clone()], sy - fy),
+        (vec![dy, -dx, zero.clone(), -ey, ex, zero], sz - fz),
+    ];
 }
 
-pub fn solve_part1(input: &str, from: f64, to: f64) -> String {
-    let hailstones: Vec<Hailstone> = input.split("\n")
-        .map(|it| it.parse::<Hailstone>().unwrap())
-        .collect();
-
-    let result = hailstones.iter().enumerate().fold(0, |acc, (idx, hs1)| {
-        return acc + hailstones[idx + 1..].iter().fold(0, |acc, hs2| {
-            if let Some(intersection) = hs1.intersect(hs2) {
-                return if intersection.0 >= from && intersection.0 <= to && intersection.1 >= from && intersection.1 <= to {
-                    acc + 1
-                } else {
-                    acc
-                };
-            }
-            return acc;
-        });
-    });
-
-    return result.to_string();
+/// Bounds of the test area on both X and Y axes, used to count intersections in the first part.
+pub struct TestArea {
+    pub from: i64,
+    pub to: i64,
 }
 
-pub fn solve_part2(input: &str) -> String {
-    let hailstones: Vec<Hailstone> = input.split("\n")
-        .map(|it| it.parse::<Hailstone>().unwrap())
-        .collect();
-
-    let bruteforce: Generator<(), (i64, i64, i64)> = Gn::new_scoped(|mut scope| {
-        let mut idx = 1;
-        loop {
-            for idy in 1..=idx {
-                for idz in 1..=idx {
-                    [-1, 1].iter().cartesian_product([-1, 1].iter().cartesian_product([-1, 1].iter())).for_each(|(x, (y, z))| {
-                        scope.yield_((idx * x, idy * y, idz * z));
-                    });
-                }
-            }
-            idx += 1;
-        }
-    });
+impl Default for TestArea {
+    fn default() -> TestArea {
+        return TestArea { from: 200000000000000, to: 400000000000000 };
+    }
+}
 
-    let hs1: &Hailstone = &hailstones[0];
-    let hs2: &Hailstone = &hailstones[1];
+const INPUT: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
 
-    let find_position = |velocity: (i64, i64, i64)| -> Option<(i64, i64, i64)> {
-        let vxd: (i64, i64) = (hs1.velocity.0 - velocity.0, hs2.velocity.0 - velocity.0);
-        let vyd: (i64, i64) = (hs1.velocity.1 - velocity.1, hs2.velocity.1 - velocity.1);
+pub struct Day24;
 
-        let div = vxd.0 * vyd.1 - vyd.0 * vxd.1;
+impl Solution for Day24 {
+    type Input = Vec<Hailstone>;
+    type Params = TestArea;
 
-        if div != 0 {
-            let time = (vyd.1 * (hs2.position.0 - hs1.position.0) - vxd.1 * (hs2.position.1 - hs1.position.1)) / div;
-            return Some((
-                hs1.position.0 + hs1.velocity.0 * time - velocity.0 * time,
-                hs1.position.1 + hs1.velocity.1 * time - velocity.1 * time,
-                hs1.position.2 + hs1.velocity.2 * time - velocity.2 * time
-            ));
-        }
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
 
-        return None;
-    };
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.lines()
+            .map(|it| parser.parse::<Hailstone>(it))
+            .collect();
+    }
 
-    let check_velocity = |position: (i64, i64, i64), velocity: (i64, i64, i64)| -> bool {
-        return hailstones.iter().all(|hs| {
-            let time: i64;
-            if hs.velocity.0 != velocity.0 {
-                time = (position.0 - hs.position.0) / (hs.velocity.0 - velocity.0);
-            } else if hs.velocity.1 != velocity.1 {
-                time = (position.1 - hs.position.1) / (hs.velocity.1 - velocity.1);
-            } else if hs.velocity.2 != velocity.2 {
-                time = (position.2 - hs.position.2) / (hs.velocity.2 - velocity.2);
-            } else {
-                panic!("Cannot resolve time for position '{:?}' and velocity '{:?}'", position, velocity)
-            }
-            return position.0 + time * velocity.0 == hs.position.0 + time * hs.velocity.0
-                && position.1 + time * velocity.1 == hs.position.1 + time * hs.velocity.1
-                && position.2 + time * velocity.2 == hs.position.2 + time * hs.velocity.2;
-        });
-    };
+    fn example_params() -> Self::Params {
+        return TestArea { from: 7, to: 27 };
+    }
 
-    for candidate in bruteforce {
-        if let Some(position) = find_position(candidate) {
-            if check_velocity(position, candidate) {
-                return (position.0 + position.1 + position.2).to_string();
-            }
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<Answer, SolveError> {
+        if params.from > params.to {
+            return Err(SolveError::InvalidParameter(format!("test area {}..{} is empty", params.from, params.to)));
         }
+        let (from, to) = (rational(params.from), rational(params.to));
+        let result: usize = par::map_range(0..input.len(), |idx| {
+            return input[idx + 1..].iter().fold(0, |acc, hs2| {
+                if let Some((x, y)) = input[idx].intersect(hs2) {
+                    return if x >= from && x <= to && y >= from && y <= to { acc + 1 } else { acc };
+                }
+                return acc;
+            });
+        }).into_iter().sum();
+
+        return Ok(Answer::from(result));
     }
 
-    panic!("Solution not found!")
+    fn part2(input: &Self::Input, _params: &Self::Params) -> Result<Answer, SolveError> {
+        // three hailstones determine the rock, unless their velocities make the equations dependent
+        let rock: Vec<BigRational> = input.windows(3).find_map(|it| {
+            let (matrix, rhs): (Vec<Vec<BigRational>>, Vec<BigRational>) = rock_equations(&it[0], &it[1]).into_iter()
+                .chain(rock_equations(&it[0], &it[2]))
+                .unzip();
+            return linalg::solve(&matrix, &rhs);
+        }).ok_or_else(|| SolveError::NoSolution("hailstones do not determine the rock position".to_string()))?;
+
+        if let Some(coordinate) = rock[0..3].iter().find(|it| !it.is_integer()) {
+            return Err(SolveError::NoSolution(format!("rock position is not an integer: {}", coordinate)));
+        }
+        let result: BigInt = rock[0..3].iter().map(|it| it.to_integer()).sum();
+        return Ok(Answer::from(result));
+    }
 }
 
 #[cfg(test)]
@@ -152,22 +156,20 @@ mod tests {
 
     use super::*;
 
-    const INPUT: &str = "19, 13, 30 @ -2,  1, -2
-18, 19, 22 @ -1, -1, -2
-20, 25, 34 @ -2, -2, -4
-12, 31, 28 @ -1, -2, -1
-20, 19, 15 @  1, -5, -3";
-
-
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT, 7f64, 27f64);
+        let result = Day24::part1(&Day24::parse(INPUT).unwrap(), &TestArea { from: 7, to: 27 }).unwrap();
         assert_eq!(result, "2");
     }
 
     #[test]
     fn test_part2() {
-        let result = solve_part2(INPUT);
+        let result = Day24::solve_part2(INPUT).unwrap();
         assert_eq!(result, "47");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day24>();
+    }
+}
@@ -1,38 +1,30 @@
 use std::cmp::min;
 use std::collections::HashMap;
-use std::path::PathBuf;
 use std::str::FromStr;
 
-use anyhow::Error;
-use clap::Parser;
+use common::error::SolveError;
+use common::par;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 use itertools::Itertools;
 use ndarray::Array2;
 use petgraph::graph::{NodeIndex, UnGraph};
 use petgraph::prelude::*;
 
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
-
-struct FlowNetwork {
+pub struct FlowNetwork {
     graph: UnGraph<String, usize>,
 }
 
 impl FromStr for FlowNetwork {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let parser: Parser = Parser::create(input);
         let mut graph: UnGraph<String, usize> = UnGraph::new_undirected();
         let mut nodes: HashMap<&str, NodeIndex> = HashMap::new();
-        let lines: Vec<&str> = input.split('\n').collect();
-
-        lines.clone().iter().for_each(|line| {
-            let parts: Vec<&str> = line.split(':').collect();
 
-            let start = parts.first().unwrap();
+        for line in parser.lines() {
+            let (start, next) = parser.key_value(line, ":")?;
             let start_idx: NodeIndex = if nodes.contains_key(start) {
                 *nodes.get(start).unwrap()
             } else {
@@ -41,7 +33,7 @@ impl FromStr for FlowNetwork {
                 idx
             };
 
-            parts.last().unwrap().trim().split(' ').for_each(|node| {
+            next.split_whitespace().for_each(|node| {
                 let node_idx: NodeIndex = if nodes.contains_key(node) {
                     *nodes.get(node).unwrap()
                 } else {
@@ -51,7 +43,7 @@ impl FromStr for FlowNetwork {
                 };
                 graph.add_edge(start_idx, node_idx, 0);
             });
-        });
+        }
 
         return Ok(FlowNetwork { graph });
     }
@@ -92,29 +84,7 @@ impl FlowNetwork {
     }
 }
 
-// based on https://brilliant.org/wiki/ford-fulkerson-algorithm/
-pub fn solve_part1(input: &str) -> String {
-    let flow_network: FlowNetwork = input.parse::<FlowNetwork>().unwrap();
-
-    let nodes_count: usize = flow_network.graph.node_count();
-    let start_idx: NodeIndex = NodeIndex::new(0);
-
-    let result = (1..nodes_count).fold((1, 0), |acc, node_idx| {
-        return if flow_network.max_flow(&start_idx, &NodeIndex::new(node_idx)) == 3 {
-            (acc.0, acc.1 + 1)
-        } else {
-            (acc.0 + 1, acc.1)
-        };
-    });
-
-    return (result.0 * result.1).to_string();
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const INPUT: &str = "jqt: rhn xhk nvd
+const INPUT: &str = "jqt: rhn xhk nvd
 rsh: frs pzl lsr
 xhk: hfx
 cmg: qnr nvd lhk bvb
@@ -128,9 +98,47 @@ lsr: lhk
 rzs: qnr cmg lsr rsh
 frs: qnr lhk lsr";
 
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = FlowNetwork;
+    type Params = ();
+
+    const PARTS: u8 = 1;
+
+    const EXAMPLES: &'static [&'static str] = &[INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return parser.parse::<FlowNetwork>(input);
+    }
+
+    // based on https://brilliant.org/wiki/ford-fulkerson-algorithm/
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        let nodes_count: usize = input.graph.node_count();
+        let start_idx: NodeIndex = NodeIndex::new(0);
+
+        let flows: Vec<i32> = par::map_range(1..nodes_count, |node_idx| input.max_flow(&start_idx, &NodeIndex::new(node_idx)));
+        let result = flows.into_iter().fold((1, 0), |acc, flow| {
+            return if flow == 3 { (acc.0, acc.1 + 1) } else { (acc.0 + 1, acc.1) };
+        });
+
+        return Ok(Answer::from(result.0 * result.1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_part1() {
-        let result = solve_part1(INPUT);
+        let result = Day25::solve_part1(INPUT).unwrap();
         assert_eq!(result, "54");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day25>();
+    }
+}
@@ -0,0 +1,72 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::registry::Day;
+
+/// Accepted answers of a single day, stored in `answers.toml` next to its `input.txt`.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    pub fn path(day: &Day, data_dir: &Path) -> PathBuf {
+        return day.dir(data_dir).join("answers.toml");
+    }
+
+    /// Loads stored answers, a missing file means no answer is known yet.
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let content: String = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(err) => return Err(format!("cannot read '{}': {}", path.display(), err)),
+        };
+
+        return toml::from_str(&content).map_err(|err| format!("invalid answers file '{}': {}", path.display(), err));
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content: String = toml::to_string(self).unwrap();
+        return fs::write(path, content).map_err(|err| format!("cannot write '{}': {}", path.display(), err));
+    }
+
+    pub fn get(&self, part: u8) -> Option<&String> {
+        return match part {
+            1 => self.part1.as_ref(),
+            _ => self.part2.as_ref(),
+        };
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part1 = Some(answer.to_string()),
+            _ => self.part2 = Some(answer.to_string()),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers: Answers = toml::from_str("part1 = \"569904\"").unwrap();
+        assert_eq!(answers.get(1), Some(&"569904".to_string()));
+        assert_eq!(answers.get(2), None);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let answers: Answers = Answers { part1: Some("TGWSMRBPN".to_string()), part2: Some("TZLTLWRNF".to_string()) };
+        let content: String = toml::to_string(&answers).unwrap();
+        assert_eq!(content, "part1 = \"TGWSMRBPN\"\npart2 = \"TZLTLWRNF\"\n");
+        assert_eq!(toml::from_str::<Answers>(&content).unwrap(), answers);
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use common::error::SolveError;
+use common::{input, Solution};
+use serde::{Deserialize, Serialize};
+
+use crate::registry::Day;
+
+/// Durations of a single run of the parse stage and of both parts.
+pub struct Sample {
+    parse: Duration,
+    part1: Duration,
+    part2: Option<Duration>,
+}
+
+/// Runs every stage of the solution once, parsing the input only once for both parts.
+pub fn measure<S: Solution>(input: &str) -> Result<Sample, SolveError> {
+    let start: Instant = Instant::now();
+    let parsed: S::Input = black_box(S::parse_input(input)?);
+    let parse: Duration = start.elapsed();
+
+    let params: S::Params = S::Params::default();
+
+    let start: Instant = Instant::now();
+    black_box(S::part1(&parsed, &params)?);
+    let part1: Duration = start.elapsed();
+
+    let part2: Option<Duration> = if S::PARTS > 1 {
+        let start: Instant = Instant::now();
+        black_box(S::part2(&parsed, &params)?);
+        Some(start.elapsed())
+    } else {
+        None
+    };
+
+    return Ok(Sample { parse, part1, part2 });
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Stats {
+    min_ns: u64,
+    median_ns: u64,
+    max_ns: u64,
+}
+
+impl Stats {
+    fn create(samples: &[Duration]) -> Stats {
+        let mut sorted: Vec<Duration> = samples.to_vec();
+        sorted.sort();
+
+        return Stats {
+            min_ns: sorted.first().unwrap().as_nanos() as u64,
+            median_ns: sorted[sorted.len() / 2].as_nanos() as u64,
+            max_ns: sorted.last().unwrap().as_nanos() as u64,
+        };
+    }
+
+    fn format(&self) -> String {
+        return format!(
+            "{:.2?} / {:.2?} / {:.2?}",
+            Duration::from_nanos(self.min_ns),
+            Duration::from_nanos(self.median_ns),
+            Duration::from_nanos(self.max_ns)
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct DayReport {
+    year: u16,
+    day: u8,
+    parse: Stats,
+    part1: Stats,
+    part2: Option<Stats>,
+}
+
+impl DayReport {
+    fn total_ns(&self) -> u64 {
+        return self.parse.median_ns + self.part1.median_ns + self.part2.as_ref().map_or(0, |it| it.median_ns);
+    }
+
+    /// Sum of the fastest runs of all stages.
+    fn best_ns(&self) -> u64 {
+        return self.parse.min_ns + self.part1.min_ns + self.part2.as_ref().map_or(0, |it| it.min_ns);
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Report {
+    iterations: u32,
+    days: Vec<DayReport>,
+    /// Sum of median durations of all stages of all days
+    total_ns: u64,
+}
+
+/// Reads the best time of every day from reports written with `--json`, keeping the fastest one over all reports.
+pub fn best_times(paths: &[PathBuf]) -> Result<HashMap<(u16, u8), Duration>, String> {
+    let mut best: HashMap<(u16, u8), Duration> = HashMap::new();
+
+    for path in paths {
+        let content: String = fs::read_to_string(path).map_err(|err| format!("cannot read '{}': {}", path.display(), err))?;
+        let report: Report = serde_json::from_str(&content).map_err(|err| format!("invalid report '{}': {}", path.display(), err))?;
+
+        for day in report.days {
+            let time: Duration = Duration::from_nanos(day.best_ns());
+            best.entry((day.year, day.day)).and_modify(|it| *it = time.min(*it)).or_insert(time);
+        }
+    }
+
+    return Ok(best);
+}
+
+fn print_row(cells: [&str; 5]) {
+    let [day, parse, part1, part2, total] = cells;
+    let row: String = format!("{:<10}{:<34}{:<34}{:<34}{}", day, parse, part1, part2, total);
+    println!("{}", row.trim_end());
+}
+
+/// Times parse, part 1 and part 2 of every given day over a number of iterations.
+pub fn bench(days: &[&Day], data_dir: &Path, iterations: u32, json: Option<&Path>) -> ExitCode {
+    let mut failed: bool = false;
+    let mut reports: Vec<DayReport> = Vec::new();
+
+    print_row(["day", "parse (min / median / max)", "part 1", "part 2", "total (median)"]);
+
+    for day in days {
+        let input: String = match input::load(&day.input_path(data_dir)) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("{} day {:02}: {}", day.year, day.day, err);
+                failed = true;
+                continue;
+            }
+        };
+
+        let samples: Vec<Sample> = match (0..iterations).map(|_| (day.bench)(&input)).collect() {
+            Ok(samples) => samples,
+            Err(err) => {
+                eprintln!("{} day {:02}: {}", day.year, day.day, err);
+                failed = true;
+                continue;
+            }
+        };
+
+        let parse: Vec<Duration> = samples.iter().map(|it| it.parse).collect();
+        let part1: Vec<Duration> = samples.iter().map(|it| it.part1).collect();
+        let part2: Vec<Duration> = samples.iter().filter_map(|it| it.part2).collect();
+
+        let report: DayReport = DayReport {
+            year: day.year,
+            day: day.day,
+            parse: Stats::create(&parse),
+            part1: Stats::create(&part1),
+            part2: if part2.is_empty() { None } else { Some(Stats::create(&part2)) },
+        };
+
+        print_row([
+            &format!("{} {:02}", report.year, report.day),
+            &report.parse.format(),
+            &report.part1.format(),
+            &report.part2.as_ref().map_or(String::from("-"), |it| it.format()),
+            &format!("{:.2?}", Duration::from_nanos(report.total_ns())),
+        ]);
+
+        reports.push(report);
+    }
+
+    let total_ns: u64 = reports.iter().map(|it| it.total_ns()).sum();
+    println!();
+    println!("total: {:.2?}", Duration::from_nanos(total_ns));
+
+    if let Some(path) = json {
+        let report: Report = Report { iterations, days: reports, total_ns };
+        let content: String = serde_json::to_string_pretty(&report).unwrap();
+        if let Err(err) = fs::write(path, content) {
+            eprintln!("cannot write '{}': {}", path.display(), err);
+            failed = true;
+        }
+    }
+
+    return if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = [5, 1, 4, 2, 3].iter().map(|it| Duration::from_nanos(*it)).collect();
+        assert_eq!(Stats::create(&samples), Stats { min_ns: 1, median_ns: 3, max_ns: 5 });
+    }
+}
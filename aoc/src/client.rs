@@ -0,0 +1,136 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable with the session cookie, takes precedence over the cookies file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = "aoc-runner (Rust, ureq)";
+const NOT_LOGGED_IN: &str = "Puzzle inputs differ by user";
+
+#[derive(Debug)]
+pub enum ClientError {
+    NoCookies(String),
+    NotLoggedIn,
+    NotAvailable(u16),
+    Http(String),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ClientError::NoCookies(reason) => write!(f, "no AoC session cookie: {}", reason),
+            ClientError::NotLoggedIn => write!(f, "not logged in, check that the session cookie is valid and not expired"),
+            ClientError::NotAvailable(status) => write!(f, "puzzle is not available (HTTP {})", status),
+            ClientError::Http(reason) => write!(f, "request failed: {}", reason),
+        };
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// Reads the `Cookie` header value from the environment or from the cookies file.
+///
+/// A bare session token (without `session=`) is accepted as well.
+pub fn load_cookies(path: &Path) -> Result<String, ClientError> {
+    let cookies: String = match std::env::var(SESSION_ENV) {
+        Ok(session) if !session.trim().is_empty() => session,
+        _ => fs::read_to_string(path).map_err(|err| {
+            ClientError::NoCookies(format!("set {} or create '{}' ({})", SESSION_ENV, path.display(), err))
+        })?,
+    };
+
+    let cookies: &str = cookies.trim();
+    if cookies.is_empty() {
+        return Err(ClientError::NoCookies(format!("'{}' is empty", path.display())));
+    }
+
+    return Ok(if cookies.contains('=') { cookies.to_string() } else { format!("session={}", cookies) });
+}
+
+/// Minimal client of the AoC website.
+pub struct Client {
+    base_url: String,
+    cookies: String,
+}
+
+impl Client {
+    pub fn create(base_url: &str, cookies: &str) -> Client {
+        return Client { base_url: base_url.trim_end_matches('/').to_string(), cookies: cookies.to_string() };
+    }
+
+    pub fn fetch_input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let url: String = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = ureq::get(&url)
+            .set("Cookie", &self.cookies)
+            .set("User-Agent", USER_AGENT)
+            .call();
+
+        let body: String = match response {
+            Ok(response) => response.into_string().map_err(|err| ClientError::Http(err.to_string()))?,
+            Err(ureq::Error::Status(status, response)) => {
+                let body: String = response.into_string().unwrap_or_default();
+                return Err(if body.contains(NOT_LOGGED_IN) { ClientError::NotLoggedIn } else { ClientError::NotAvailable(status) });
+            }
+            Err(err) => return Err(ClientError::Http(err.to_string())),
+        };
+
+        if body.contains(NOT_LOGGED_IN) {
+            return Err(ClientError::NotLoggedIn);
+        }
+
+        return Ok(body);
+    }
+
+    /// Posts the answer of a part and returns the response page.
+    pub fn submit_answer(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<String, ClientError> {
+        let url: String = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let response = ureq::post(&url)
+            .set("Cookie", &self.cookies)
+            .set("User-Agent", USER_AGENT)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        return match response {
+            Ok(response) => response.into_string().map_err(|err| ClientError::Http(err.to_string())),
+            Err(ureq::Error::Status(status, response)) => {
+                let body: String = response.into_string().unwrap_or_default();
+                Err(if body.contains(NOT_LOGGED_IN) { ClientError::NotLoggedIn } else { ClientError::NotAvailable(status) })
+            }
+            Err(err) => Err(ClientError::Http(err.to_string())),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stub;
+
+    use super::*;
+
+    #[test]
+    fn test_fetch_input() {
+        let server = stub::serve(vec![(200, "1000\n2000\n")]);
+        let client: Client = Client::create(&server.base_url, "session=abc");
+
+        let result = client.fetch_input(2022, 1).unwrap();
+        assert_eq!(result, "1000\n2000\n");
+
+        let requests: Vec<String> = server.requests();
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("Cookie: session=abc"));
+    }
+
+    #[test]
+    fn test_fetch_input_errors() {
+        let server = stub::serve(vec![
+            (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            (404, "Please don't repeatedly request this endpoint before it unlocks!"),
+        ]);
+        let client: Client = Client::create(&server.base_url, "session=abc");
+
+        assert!(matches!(client.fetch_input(2023, 1), Err(ClientError::NotLoggedIn)));
+        assert!(matches!(client.fetch_input(2023, 2), Err(ClientError::NotAvailable(404))));
+    }
+}
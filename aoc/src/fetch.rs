@@ -0,0 +1,78 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::client::{load_cookies, Client};
+
+/// Where a fetched puzzle input came from.
+#[derive(Debug, PartialEq)]
+pub enum Origin {
+    /// `input.txt` already exists, nothing was done
+    Existing,
+    /// copied from the local cache of downloaded inputs
+    Cache,
+    /// downloaded from the website
+    Download,
+}
+
+pub fn cache_path(cache_dir: &Path, year: u16, day: u8) -> PathBuf {
+    return cache_dir.join(year.to_string()).join(format!("day-{:02}", day)).join("input.txt");
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| format!("cannot create '{}': {}", parent.display(), err))?;
+    }
+    return fs::write(path, content).map_err(|err| format!("cannot write '{}': {}", path.display(), err));
+}
+
+/// Makes sure the puzzle input exists at `input_path`, the website is asked only once per puzzle.
+pub fn fetch(year: u16, day: u8, input_path: &Path, cache_dir: &Path, base_url: &str, cookies_path: &Path) -> Result<Origin, String> {
+    if input_path.exists() {
+        return Ok(Origin::Existing);
+    }
+
+    let cached: PathBuf = cache_path(cache_dir, year, day);
+    let (content, origin) = match fs::read_to_string(&cached) {
+        Ok(content) => (content, Origin::Cache),
+        Err(_) => {
+            let cookies: String = load_cookies(cookies_path).map_err(|err| err.to_string())?;
+            let content: String = Client::create(base_url, &cookies).fetch_input(year, day).map_err(|err| err.to_string())?;
+            write(&cached, &content)?;
+            (content, Origin::Download)
+        }
+    };
+
+    // inputs are stored without the trailing newline
+    write(input_path, content.trim_end_matches('\n'))?;
+    return Ok(origin);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stub;
+
+    use super::*;
+
+    #[test]
+    fn test_fetch() {
+        let dir: PathBuf = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let cookies: PathBuf = dir.join("aoc-cookies.txt");
+        let cache_dir: PathBuf = dir.join("cache");
+        write(&cookies, "session=abc").unwrap();
+
+        // the stub serves a single response, so later calls must not hit the server
+        let server = stub::serve(vec![(200, "1000\n2000\n")]);
+
+        let input: PathBuf = dir.join("2022").join("day-01").join("input.txt");
+        assert_eq!(fetch(2022, 1, &input, &cache_dir, &server.base_url, &cookies), Ok(Origin::Download));
+        assert_eq!(fs::read_to_string(&input).unwrap(), "1000\n2000");
+        assert_eq!(fetch(2022, 1, &input, &cache_dir, &server.base_url, &cookies), Ok(Origin::Existing));
+
+        fs::remove_file(&input).unwrap();
+        assert_eq!(fetch(2022, 1, &input, &cache_dir, &server.base_url, &cookies), Ok(Origin::Cache));
+        assert_eq!(fs::read_to_string(&input).unwrap(), "1000\n2000");
+
+        assert_eq!(server.requests().len(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
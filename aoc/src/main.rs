@@ -0,0 +1,263 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use common::report::Format;
+use common::{input, Answer};
+
+use crate::client::Client;
+use crate::fetch::Origin;
+use crate::registry::{Day, Solver};
+use crate::run::Source;
+use crate::submit::Outcome;
+
+mod answers;
+mod bench;
+mod client;
+mod fetch;
+mod readme;
+mod registry;
+mod run;
+mod scaffold;
+mod submit;
+mod verify;
+
+#[cfg(test)]
+mod stub;
+
+#[derive(Parser)]
+#[clap(author, version, about, long_about = None)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run solutions of a single day or of a whole year
+    Run {
+        #[clap(value_parser)]
+        year: u16,
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25), required_unless_present = "all")]
+        day: Option<u8>,
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Run every registered day of the year
+        #[clap(long, conflicts_with = "day")]
+        all: bool,
+        #[clap(short, long, value_parser, default_value = "./")]
+        data_dir: PathBuf,
+        /// Read the puzzle input of a single day from the given file, `-` reads it from stdin
+        #[clap(short, long, value_parser, conflicts_with_all = ["all", "example"])]
+        input: Option<PathBuf>,
+        /// Solve the examples from the puzzle descriptions instead of the puzzle inputs
+        #[clap(short, long)]
+        example: bool,
+        /// Print answers as text, as a JSON document or as one JSON record per line
+        #[clap(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Print hits and misses of the memoised functions to stderr, counted in debug builds only
+        #[clap(long)]
+        memo_stats: bool,
+    },
+    /// Check solutions against the answers stored in `answers.toml` of every day
+    Verify {
+        /// Verify a single year only
+        #[clap(value_parser)]
+        year: Option<u16>,
+        /// Verify a single day of the year only
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25), requires = "year")]
+        day: Option<u8>,
+        #[clap(short, long, value_parser, default_value = "./")]
+        data_dir: PathBuf,
+    },
+    /// Time parse, part 1 and part 2 of every day of a year
+    Bench {
+        #[clap(value_parser)]
+        year: u16,
+        /// Benchmark a single day of the year only
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Number of runs of every stage
+        #[clap(short = 'n', long, value_parser = clap::value_parser!(u32).range(1..), default_value = "5")]
+        iterations: u32,
+        /// Also write the results as JSON to the given file
+        #[clap(long, value_parser)]
+        json: Option<PathBuf>,
+        #[clap(short, long, value_parser, default_value = "./")]
+        data_dir: PathBuf,
+    },
+    /// Download the puzzle input of a day into `<year>/day-<dd>/input.txt`
+    Fetch {
+        #[clap(value_parser)]
+        year: u16,
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[clap(short, long, value_parser, default_value = "./")]
+        data_dir: PathBuf,
+        /// Directory with already downloaded inputs, so that every input is downloaded only once
+        #[clap(long, value_parser, default_value = ".aoc-cache")]
+        cache_dir: PathBuf,
+        /// URL of the AoC website
+        #[clap(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+        base_url: String,
+        /// File with the AoC `session` cookie, ignored when `AOC_SESSION` is set
+        #[clap(long, value_parser, default_value = "aoc-cookies.txt")]
+        cookies: PathBuf,
+    },
+    /// Solve a part of a day and submit the answer, unless it is already known to be wrong
+    Submit {
+        #[clap(value_parser)]
+        year: u16,
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        #[clap(short, long, value_parser, default_value = "./")]
+        data_dir: PathBuf,
+        /// URL of the AoC website
+        #[clap(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+        base_url: String,
+        /// File with the AoC `session` cookie, ignored when `AOC_SESSION` is set
+        #[clap(long, value_parser, default_value = "aoc-cookies.txt")]
+        cookies: PathBuf,
+    },
+    /// Regenerate star counts and calendars of the READMEs from the stored answers
+    Readme {
+        #[clap(short, long, value_parser, default_value = "./")]
+        data_dir: PathBuf,
+        /// Reports written by `bench --json`, the best time of every day is shown in the calendar
+        #[clap(long, value_parser)]
+        bench: Vec<PathBuf>,
+    },
+    /// Create the crate of a new day from `daily-template` and register it in the runner
+    New {
+        #[clap(value_parser)]
+        year: u16,
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Root of the repository
+        #[clap(short, long, value_parser, default_value = "./")]
+        data_dir: PathBuf,
+        #[clap(long, value_parser, default_value = "daily-template")]
+        template: PathBuf,
+        /// Saved puzzle page, its first example becomes the `INPUT` of the tests
+        #[clap(long, value_parser)]
+        puzzle: Option<PathBuf>,
+    },
+}
+
+/// Resolves registered days of the given year (or of all years), or a single day.
+fn select_days(year: Option<u16>, day: Option<u8>) -> Result<Vec<&'static Day>, String> {
+    let days: Vec<&Day> = match (year, day) {
+        (Some(year), Some(day)) => match registry::find(year, day) {
+            Some(it) => vec![it],
+            None => return Err(format!("No solution registered for {} day {:02}", year, day)),
+        },
+        (Some(year), None) => registry::for_year(year).collect(),
+        (None, _) => registry::DAYS.iter().collect(),
+    };
+
+    if days.is_empty() {
+        return Err(format!("No solutions registered for {}", year.unwrap()));
+    }
+
+    return Ok(days);
+}
+
+/// Runs the command over the selected days, reporting days which are not registered.
+fn with_days(year: Option<u16>, day: Option<u8>, command: impl FnOnce(&[&Day]) -> ExitCode) -> ExitCode {
+    return match select_days(year, day) {
+        Ok(days) => command(&days),
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    };
+}
+
+fn submit(day: &Day, part: u8, data_dir: &Path, base_url: &str, cookies: &Path) -> ExitCode {
+    let solve: Solver = match day.parts().into_iter().find(|(it, _)| *it == part) {
+        Some((_, solve)) => solve,
+        None => {
+            eprintln!("{} day {:02} has no part {}", day.year, day.day, part);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result: Result<Outcome, String> = input::load(&day.input_path(data_dir))
+        .map_err(|err| err.to_string())
+        .and_then(|input| {
+            let answer: Answer = solve(&input).map_err(|err| err.to_string())?;
+            println!("{} day {:02} part {}: {}", day.year, day.day, part, answer);
+            let cookies: String = client::load_cookies(cookies).map_err(|err| err.to_string())?;
+            return submit::submit(&Client::create(base_url, &cookies), day, data_dir, part, &answer);
+        });
+
+    return match result {
+        Ok(outcome) => {
+            println!("{}", outcome);
+            if matches!(outcome, Outcome::Right | Outcome::Solved) { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        }
+        Err(err) => {
+            eprintln!("{} day {:02}: {}", day.year, day.day, err);
+            ExitCode::FAILURE
+        }
+    };
+}
+
+fn main() -> ExitCode {
+    let args: Cli = Cli::parse();
+
+    return match args.command {
+        Command::Run { year, day, part, data_dir, input, example, format, memo_stats, .. } => {
+            let source: Source = match (input, example) {
+                (_, true) => Source::Example,
+                (Some(path), false) => Source::File(path),
+                (None, false) => Source::DataDir(data_dir),
+            };
+
+            with_days(Some(year), day, |days| run::run(days, part, &source, format, memo_stats))
+        }
+        Command::Verify { year, day, data_dir } => {
+            with_days(year, day, |days| verify::verify(days, &data_dir))
+        }
+        Command::Bench { year, day, iterations, json, data_dir } => {
+            with_days(Some(year), day, |days| bench::bench(days, &data_dir, iterations, json.as_deref()))
+        }
+        Command::Fetch { year, day, data_dir, cache_dir, base_url, cookies } => {
+            let input_path: PathBuf = registry::day_dir(&data_dir, year, day).join("input.txt");
+            match fetch::fetch(year, day, &input_path, &cache_dir, &base_url, &cookies) {
+                Ok(origin) => {
+                    let how: &str = match origin {
+                        Origin::Existing => "already exists",
+                        Origin::Cache => "restored from cache",
+                        Origin::Download => "downloaded",
+                    };
+                    println!("Input {}: {}", how, input_path.display());
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    eprintln!("{} day {:02}: {}", year, day, err);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Submit { year, day, part, data_dir, base_url, cookies } => {
+            with_days(Some(year), Some(day), |days| submit(days[0], part, &data_dir, &base_url, &cookies))
+        }
+        Command::Readme { data_dir, bench } => readme::readme(&data_dir, &bench),
+        Command::New { year, day, data_dir, template, puzzle } => {
+            match scaffold::create(&data_dir, &data_dir.join(template), year, day, puzzle.as_deref()) {
+                Ok(path) => {
+                    println!("Created {}", path.display());
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    eprintln!("{} day {:02}: {}", year, day, err);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+    };
+}
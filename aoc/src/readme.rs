@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use crate::answers::Answers;
+use crate::registry;
+
+const DAY_TITLE: &str = "<span title=\"Day ";
+
+/// What is known about a single day of the calendar.
+#[derive(Debug, PartialEq)]
+struct DayState {
+    stars: u8,
+    solution: bool,
+    best: Option<Duration>,
+}
+
+/// Stars of every day of the year, from the answers accepted so far.
+///
+/// The second star of day 25 is given for free once all the other stars are collected.
+fn stars(answers: &[Answers]) -> Vec<u8> {
+    let mut stars: Vec<u8> = answers.iter().map(|it| it.part1.is_some() as u8 + it.part2.is_some() as u8).collect();
+
+    let others: u32 = stars.iter().take(24).map(|it| *it as u32).sum();
+    if stars.len() == 25 && stars[24] == 1 && others == 48 {
+        stars[24] = 2;
+    }
+
+    return stars;
+}
+
+fn title(day: u8, stars: u8) -> String {
+    return match stars {
+        0 => format!("Day {}", day),
+        1 => format!("Day {}, one star", day),
+        _ => format!("Day {}, two stars", day),
+    };
+}
+
+/// Regenerates a calendar entry of a day, keeping its art.
+///
+/// The entry starts with the `<span title="Day N...">` of the day and ends with the links after the day number.
+fn render_day(entry: &str, year: u16, day: u8, state: &DayState) -> String {
+    let (body, newlines) = entry.split_at(entry.trim_end_matches('\n').len());
+
+    let title_start: usize = DAY_TITLE.len() - "Day ".len();
+    let title_end: usize = title_start + body[title_start..].find('"').unwrap_or(0);
+    let marker: String = format!("<span>{:>2}</span> ", day);
+    let suffix_start: usize = body.rfind(&marker).unwrap_or(body.len());
+
+    let old_suffix: &str = &body[suffix_start..];
+    let best: Option<String> = match state.best {
+        Some(best) => Some(format!("{:.2?}", best)),
+        // keep the time of the last run with benchmark results
+        None => old_suffix.find("<i>").and_then(|start| {
+            let end: usize = start + old_suffix[start..].find("</i>")?;
+            return Some(old_suffix[start + 3..end].to_string());
+        }),
+    };
+
+    let mut suffix: String = marker;
+    for star in 0..2 {
+        suffix.push_str(if star < state.stars { "<b>*</b>" } else { "<span>-</span>" });
+    }
+    suffix.push_str(&format!(" | <a href=\"https://adventofcode.com/{}/day/{}\" target=\"_blank\">puzzle</a>", year, day));
+    if state.solution {
+        suffix.push_str(&format!(" | <a href=\"day-{:02}/src/lib.rs\">solution</a>", day));
+    }
+    if let Some(best) = best {
+        suffix.push_str(&format!(" | <i>{}</i>", best));
+    }
+    suffix.push_str("</span>");
+
+    return format!("{}{}{}{}{}", &body[..title_start], title(day, state.stars), &body[title_end..suffix_start], suffix, newlines);
+}
+
+/// Regenerates every day entry of the `<pre>` calendar of a year README.
+fn render_calendar(readme: &str, year: u16, states: &[DayState]) -> String {
+    let (start, end) = match (readme.find("<pre>"), readme.find("</pre>")) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => return readme.to_string(),
+    };
+
+    let calendar: &str = &readme[start..end];
+    let mut starts: Vec<usize> = calendar.match_indices(DAY_TITLE).map(|(idx, _)| idx).collect();
+    let mut result: String = calendar[..starts.first().copied().unwrap_or(calendar.len())].to_string();
+    starts.push(calendar.len());
+
+    for window in starts.windows(2) {
+        let entry: &str = &calendar[window[0]..window[1]];
+        let day: Option<u8> = entry[DAY_TITLE.len()..].split(|c: char| !c.is_ascii_digit()).next().and_then(|it| it.parse().ok());
+
+        match day {
+            Some(day @ 1..=25) => result.push_str(&render_day(entry, year, day, &states[day as usize - 1])),
+            _ => result.push_str(entry),
+        }
+    }
+
+    return format!("{}{}{}", &readme[..start], result, &readme[end..]);
+}
+
+/// Updates the `**N**⭐` total of the year in the list of events.
+fn render_events(readme: &str, year: u16, total: u32) -> String {
+    let prefix: String = format!("* [[{}]]", year);
+
+    let lines: Vec<String> = readme.split('\n').map(|line| {
+        if !line.starts_with(&prefix) {
+            return line.to_string();
+        }
+        return match (line.find("**"), line.rfind("**⭐")) {
+            (Some(start), Some(end)) if start < end => format!("{}**{}**⭐{}", &line[..start], total, &line[end + "**⭐".len()..]),
+            _ => line.to_string(),
+        };
+    }).collect();
+
+    return lines.join("\n");
+}
+
+/// Writes the file only when its content changes.
+fn update(path: &Path, content: &str, updated: &mut Vec<PathBuf>) -> Result<(), String> {
+    let current: String = fs::read_to_string(path).map_err(|err| format!("cannot read '{}': {}", path.display(), err))?;
+    if current != content {
+        fs::write(path, content).map_err(|err| format!("cannot write '{}': {}", path.display(), err))?;
+        updated.push(path.to_path_buf());
+    }
+    return Ok(());
+}
+
+fn regenerate(data_dir: &Path, best: &HashMap<(u16, u8), Duration>) -> Result<Vec<PathBuf>, String> {
+    let main_path: PathBuf = data_dir.join("README.md");
+    let mut main: String = fs::read_to_string(&main_path).map_err(|err| format!("cannot read '{}': {}", main_path.display(), err))?;
+    let mut updated: Vec<PathBuf> = Vec::new();
+
+    let mut years: Vec<u16> = registry::DAYS.iter().map(|it| it.year).collect();
+    years.dedup();
+
+    for year in years {
+        let answers: Vec<Answers> = (1..=25)
+            .map(|day| Answers::load(&registry::day_dir(data_dir, year, day).join("answers.toml")))
+            .collect::<Result<_, _>>()?;
+        let stars: Vec<u8> = stars(&answers);
+
+        let states: Vec<DayState> = (1..=25).map(|day| DayState {
+            stars: stars[day as usize - 1],
+            solution: registry::day_dir(data_dir, year, day).join("src").join("lib.rs").exists(),
+            best: best.get(&(year, day)).copied(),
+        }).collect();
+
+        let year_path: PathBuf = data_dir.join(year.to_string()).join("README.md");
+        if let Ok(readme) = fs::read_to_string(&year_path) {
+            update(&year_path, &render_calendar(&readme, year, &states), &mut updated)?;
+        }
+
+        main = render_events(&main, year, stars.iter().map(|it| *it as u32).sum());
+    }
+
+    update(&main_path, &main, &mut updated)?;
+    return Ok(updated);
+}
+
+/// Regenerates star counts and calendars of the READMEs from the stored answers and benchmark reports.
+pub fn readme(data_dir: &Path, bench: &[PathBuf]) -> ExitCode {
+    let result: Result<Vec<PathBuf>, String> = crate::bench::best_times(bench).and_then(|best| regenerate(data_dir, &best));
+
+    return match result {
+        Ok(updated) => {
+            for path in &updated {
+                println!("Updated {}", path.display());
+            }
+            if updated.is_empty() {
+                println!("READMEs are up to date");
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stars() {
+        let solved = || Answers { part1: Some("1".to_string()), part2: Some("2".to_string()) };
+
+        let mut answers: Vec<Answers> = (0..24).map(|_| solved()).collect();
+        answers.push(Answers { part1: Some("1".to_string()), part2: None });
+        assert_eq!(stars(&answers).iter().map(|it| *it as u32).sum::<u32>(), 50);
+
+        answers[3].part2 = None;
+        assert_eq!(stars(&answers)[3..].iter().map(|it| *it as u32).sum::<u32>(), 1 + 20 * 2 + 1);
+    }
+
+    #[test]
+    fn test_render_calendar() {
+        let readme: &str = "## Calendar\n<pre><span title=\"Day 25\">  - /\\ -\n@@#  <span>25</span> <span>-</span><span>-</span> | \
+            <a href=\"https://adventofcode.com/2022/25\" target=\"_blank\">puzzle</a></span>\n\
+            <span title=\"Day 1, two stars\" ><span>~ ~</span>  <span> 1</span> <b>*</b><b>*</b> | \
+            <a href=\"https://adventofcode.com/2022/day/1\" target=\"_blank\">puzzle</a> | <i>1.00ms</i></span>\n</pre>\n";
+
+        let mut states: Vec<DayState> = (0..25).map(|_| DayState { stars: 0, solution: false, best: None }).collect();
+        states[0] = DayState { stars: 2, solution: true, best: None };
+        states[24] = DayState { stars: 1, solution: true, best: Some(Duration::from_micros(1500)) };
+
+        let expected: &str = "## Calendar\n<pre><span title=\"Day 25, one star\">  - /\\ -\n@@#  <span>25</span> <b>*</b><span>-</span> | \
+            <a href=\"https://adventofcode.com/2022/day/25\" target=\"_blank\">puzzle</a> | <a href=\"day-25/src/lib.rs\">solution</a> | \
+            <i>1.50ms</i></span>\n\
+            <span title=\"Day 1, two stars\" ><span>~ ~</span>  <span> 1</span> <b>*</b><b>*</b> | \
+            <a href=\"https://adventofcode.com/2022/day/1\" target=\"_blank\">puzzle</a> | <a href=\"day-01/src/lib.rs\">solution</a> | \
+            <i>1.00ms</i></span>\n</pre>\n";
+        assert_eq!(render_calendar(readme, 2022, &states), expected);
+
+        assert_eq!(render_events("* [[2022]](2022/README.md) **10**⭐\n", 2022, 12), "* [[2022]](2022/README.md) **12**⭐\n");
+    }
+}
@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+
+use common::error::SolveError;
+use common::report::{self, Timed};
+use common::{Answer, Solution};
+
+use crate::bench::{measure, Sample};
+
+/// Directory of a day crate, `<data_dir>/<year>/day-<dd>`.
+pub fn day_dir(data_dir: &Path, year: u16, day: u8) -> PathBuf {
+    return data_dir.join(year.to_string()).join(format!("day-{:02}", day));
+}
+
+/// Solves one part of a puzzle given the raw input.
+pub type Solver = fn(&str) -> Result<Answer, SolveError>;
+
+/// Solutions of a single puzzle day, solved with the default parameters of the real puzzle.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub part1: Solver,
+    pub part2: Option<Solver>,
+    /// Solves the given part of the input, or of its example from the puzzle description, timing every stage.
+    pub timed: fn(u8, Option<&str>) -> Option<Timed>,
+    /// Times every stage of the solution once.
+    pub bench: fn(&str) -> Result<Sample, SolveError>,
+}
+
+impl Day {
+    /// Directory of the day crate, holding its puzzle input and answers.
+    pub fn dir(&self, data_dir: &Path) -> PathBuf {
+        return day_dir(data_dir, self.year, self.day);
+    }
+
+    pub fn input_path(&self, data_dir: &Path) -> PathBuf {
+        return self.dir(data_dir).join("input.txt");
+    }
+
+    pub fn parts(&self) -> Vec<(u8, Solver)> {
+        let mut parts: Vec<(u8, Solver)> = vec![(1, self.part1)];
+        if let Some(part2) = self.part2 {
+            parts.push((2, part2));
+        }
+        return parts;
+    }
+}
+
+macro_rules! day {
+    ($year:literal, $day:literal, $solution:ty) => {
+        Day {
+            year: $year,
+            day: $day,
+            part1: <$solution as Solution>::solve_part1,
+            part2: if <$solution as Solution>::PARTS > 1 { Some(<$solution as Solution>::solve_part2) } else { None },
+            timed: report::solve_timed::<$solution>,
+            bench: measure::<$solution>,
+        }
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    day!(2022, 1, aoc2022_day_01::Day01),
+    day!(2022, 2, aoc2022_day_02::Day02),
+    day!(2022, 3, aoc2022_day_03::Day03),
+    day!(2022, 4, aoc2022_day_04::Day04),
+    day!(2022, 5, aoc2022_day_05::Day05),
+    day!(2023, 1, aoc2023_day_01::Day01),
+    day!(2023, 2, aoc2023_day_02::Day02),
+    day!(2023, 3, aoc2023_day_03::Day03),
+    day!(2023, 4, aoc2023_day_04::Day04),
+    day!(2023, 5, aoc2023_day_05::Day05),
+    day!(2023, 6, aoc2023_day_06::Day06),
+    day!(2023, 7, aoc2023_day_07::Day07),
+    day!(2023, 8, aoc2023_day_08::Day08),
+    day!(2023, 9, aoc2023_day_09::Day09),
+    day!(2023, 10, aoc2023_day_10::Day10),
+    day!(2023, 11, aoc2023_day_11::Day11),
+    day!(2023, 12, aoc2023_day_12::Day12),
+    day!(2023, 13, aoc2023_day_13::Day13),
+    day!(2023, 14, aoc2023_day_14::Day14),
+    day!(2023, 15, aoc2023_day_15::Day15),
+    day!(2023, 16, aoc2023_day_16::Day16),
+    day!(2023, 17, aoc2023_day_17::Day17),
+    day!(2023, 18, aoc2023_day_18::Day18),
+    day!(2023, 19, aoc2023_day_19::Day19),
+    day!(2023, 20, aoc2023_day_20::Day20),
+    day!(2023, 21, aoc2023_day_21::Day21),
+    day!(2023, 22, aoc2023_day_22::Day22),
+    day!(2023, 23, aoc2023_day_23::Day23),
+    day!(2023, 24, aoc2023_day_24::Day24),
+    day!(2023, 25, aoc2023_day_25::Day25),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    return DAYS.iter().find(|it| it.year == year && it.day == day);
+}
+
+pub fn for_year(year: u16) -> impl Iterator<Item = &'static Day> {
+    return DAYS.iter().filter(move |it| it.year == year);
+}
@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use common::error::SolveError;
+use common::input::{self, InputError};
+use common::memo;
+use common::par;
+use common::report::{self, Format, Record, Status};
+
+use crate::registry::Day;
+
+/// Where the puzzle input of the solved days comes from.
+pub enum Source {
+    /// `<data_dir>/<year>/day-<dd>/input.txt` of every day
+    DataDir(PathBuf),
+    /// Single file (or stdin) given explicitly
+    File(PathBuf),
+    /// Examples from the puzzle descriptions
+    Example,
+}
+
+/// Prints the result of a part as a line of text, errors go to stderr.
+fn print_text(record: &Record) {
+    let prefix: String = format!("{} day {:02} part {}", record.year, record.day, record.part);
+    match (record.status, &record.answer, &record.error) {
+        (Status::Solved, Some(answer), _) => {
+            let elapsed: Duration = Duration::from_nanos(record.parse_ns.unwrap_or(0) + record.solve_ns.unwrap_or(0));
+            println!("{}: {} ({:.2?})", prefix, answer, elapsed);
+        }
+        (Status::NoExample, _, _) => println!("{}: no example", prefix),
+        (_, _, error) => eprintln!("{}: {}", prefix, error.as_deref().unwrap_or("failed")),
+    }
+}
+
+/// Input of a day loaded from the source, `None` when solving the examples.
+type Input = Result<Option<String>, InputError>;
+
+fn load(day: &Day, source: &Source) -> Input {
+    return match source {
+        Source::DataDir(data_dir) => input::load(&day.input_path(data_dir)).map(Some),
+        Source::File(path) => input::load(path).map(Some),
+        Source::Example => Ok(None),
+    };
+}
+
+/// Solves the given part of every day, concurrently with the `parallel` feature, and prints the results in order.
+///
+/// With `memo_stats` the lookups of all memos are printed to stderr afterwards.
+pub fn run(days: &[&Day], part: Option<u8>, source: &Source, format: Format, memo_stats: bool) -> ExitCode {
+    let inputs: Vec<Input> = days.iter().map(|day| load(day, source)).collect();
+
+    let jobs: Vec<(&Day, u8, &Input)> = days.iter().zip(&inputs)
+        .flat_map(|(day, input)| {
+            return day.parts().into_iter()
+                .filter(|(idx, _)| part.is_none_or(|it| it == *idx))
+                .map(move |(idx, _)| (*day, idx, input));
+        })
+        .collect();
+
+    // Only a part none of the days has, e.g. part 2 of the last day
+    if jobs.is_empty() {
+        let errors: Vec<SolveError> = days.iter().map(|day| {
+            let parts: usize = day.parts().len();
+            let plural: &str = if parts == 1 { "" } else { "s" };
+            return SolveError::InvalidParameter(format!("{} day {:02} has only {} part{}", day.year, day.day, parts, plural));
+        }).collect();
+        errors.iter().for_each(|it| eprintln!("{}", it));
+        return errors.first().map_or(ExitCode::FAILURE, |it| ExitCode::from(it.exit_code()));
+    }
+
+    let records: Vec<Record> = par::map(&jobs, |(day, idx, input)| {
+        return match input {
+            Ok(input) => Record::create(day.year, day.day, *idx, (day.timed)(*idx, input.as_deref()).as_ref()),
+            Err(err) => Record::failed(day.year, day.day, *idx, Status::Failed, Some(err.to_string())),
+        };
+    });
+
+    match format {
+        Format::Text => {
+            records.iter().for_each(print_text);
+            if days.len() > 1 {
+                let total: u64 = records.iter().map(|it| it.parse_ns.unwrap_or(0) + it.solve_ns.unwrap_or(0)).sum();
+                println!("total: {:.2?}", Duration::from_nanos(total));
+            }
+        }
+        Format::Json => println!("{}", report::to_document(&records)),
+        Format::Ndjson => records.iter().for_each(|it| println!("{}", it.to_line())),
+    }
+
+    if memo_stats {
+        match memo::total_stats() {
+            Some(stats) => eprintln!("memo: {}", stats),
+            None => eprintln!("memo: lookups are only counted in debug builds"),
+        }
+    }
+
+    return match records.iter().find(|it| it.status == Status::Failed) {
+        Some(record) => ExitCode::from(record.exit_code),
+        None => ExitCode::SUCCESS,
+    };
+}
@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::registry;
+
+/// Renders the `daily-template` placeholders for the given day.
+fn render(template: &str, year: u16, day: u8) -> String {
+    return template
+        .replace("{{project-name}}", &format!("aoc{}-day-{:02}", year, day))
+        .replace("{{crate_name}}", &format!("aoc{}_day_{:02}", year, day))
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day_number}}", &day.to_string())
+        .replace("{{day}}", &format!("{:02}", day));
+}
+
+/// Copies the template directory into `target`, rendering every file.
+fn render_dir(template_dir: &Path, target: &Path, year: u16, day: u8) -> Result<(), String> {
+    fs::create_dir_all(target).map_err(|err| format!("cannot create '{}': {}", target.display(), err))?;
+
+    let entries = fs::read_dir(template_dir).map_err(|err| format!("cannot read '{}': {}", template_dir.display(), err))?;
+    for entry in entries {
+        let path: PathBuf = entry.map_err(|err| err.to_string())?.path();
+        let destination: PathBuf = target.join(path.file_name().unwrap());
+
+        if path.is_dir() {
+            render_dir(&path, &destination, year, day)?;
+        } else {
+            let template: String = fs::read_to_string(&path).map_err(|err| format!("cannot read '{}': {}", path.display(), err))?;
+            write(&destination, &render(&template, year, day))?;
+        }
+    }
+
+    return Ok(());
+}
+
+/// Inserts `line` among the lines with a key, keeping them ordered by the key.
+///
+/// Returns `None` when a line with the same key is already there.
+fn insert_sorted<K: Ord>(content: &str, line: &str, key: impl Fn(&str) -> Option<K>) -> Option<String> {
+    let new_key: K = key(line).unwrap();
+    let lines: Vec<&str> = content.split('\n').collect();
+
+    let keyed: Vec<(usize, K)> = lines.iter().enumerate().filter_map(|(idx, it)| key(it).map(|k| (idx, k))).collect();
+    if keyed.iter().any(|(_, k)| *k == new_key) {
+        return None;
+    }
+
+    let idx: usize = match keyed.iter().find(|(_, k)| *k > new_key) {
+        Some((idx, _)) => *idx,
+        None => keyed.last().map_or(lines.len(), |(idx, _)| idx + 1),
+    };
+
+    let mut lines: Vec<&str> = lines;
+    lines.insert(idx, line);
+    return Some(lines.join("\n"));
+}
+
+/// `(year, day)` of a dependency line like `aoc2023-day-07 = { path = "../2023/day-07" }`.
+fn manifest_key(line: &str) -> Option<(u16, u8)> {
+    let name: &str = line.strip_prefix("aoc")?.split(' ').next()?;
+    let (year, day) = name.split_once("-day-")?;
+    return Some((year.parse().ok()?, day.parse().ok()?));
+}
+
+/// `(year, day)` of a registry line like `    day!(2023, 7, aoc2023_day_07::Day07),`.
+fn registry_key(line: &str) -> Option<(u16, u8)> {
+    let args: &str = line.trim().strip_prefix("day!(")?;
+    let mut parts = args.split(',').map(str::trim);
+    return Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?));
+}
+
+/// Content of the file at `path` with `line` registered in it.
+fn register(path: &Path, line: &str, key: impl Fn(&str) -> Option<(u16, u8)>) -> Result<String, String> {
+    let content: String = fs::read_to_string(path).map_err(|err| format!("cannot read '{}': {}", path.display(), err))?;
+    return insert_sorted(&content, line, key).ok_or(format!("day is already registered in '{}'", path.display()));
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    return fs::write(path, content).map_err(|err| format!("cannot write '{}': {}", path.display(), err));
+}
+
+fn unescape_html(text: &str) -> String {
+    let mut result: String = String::new();
+    let mut in_tag: bool = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+
+    return result
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+}
+
+/// First `<pre><code>` block of the puzzle description, with the trailing newline trimmed.
+fn extract_example(html: &str) -> Option<String> {
+    let start: usize = html.find("<pre><code>")? + "<pre><code>".len();
+    let end: usize = start + html[start..].find("</code></pre>")?;
+    return Some(unescape_html(&html[start..end]).trim_end_matches('\n').to_string());
+}
+
+/// Formats the example the way the day crates do, as a plain multiline string literal.
+fn string_literal(text: &str) -> String {
+    return format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+}
+
+/// Creates the crate of a new day from the template and registers it in the runner.
+pub fn create(root: &Path, template_dir: &Path, year: u16, day: u8, puzzle: Option<&Path>) -> Result<PathBuf, String> {
+    let target: PathBuf = registry::day_dir(root, year, day);
+    if target.exists() {
+        return Err(format!("'{}' already exists", target.display()));
+    }
+
+    let example: Option<String> = match puzzle {
+        Some(path) => {
+            let html: String = fs::read_to_string(path).map_err(|err| format!("cannot read '{}': {}", path.display(), err))?;
+            Some(extract_example(&html).ok_or(format!("no example found in '{}'", path.display()))?)
+        }
+        None => None,
+    };
+
+    let manifest: PathBuf = root.join("aoc").join("Cargo.toml");
+    let registry: PathBuf = root.join("aoc").join("src").join("registry.rs");
+    let crate_name: String = format!("aoc{}_day_{:02}", year, day);
+
+    // everything is checked before the first write, so a failure leaves the tree untouched
+    let manifest_content: String =
+        register(&manifest, &format!("aoc{}-day-{:02} = {{ path = \"../{}/day-{:02}\" }}", year, day, year, day), manifest_key)?;
+    let registry_content: String =
+        register(&registry, &format!("    day!({}, {}, {}::Day{:02}),", year, day, crate_name, day), registry_key)?;
+
+    render_dir(template_dir, &target, year, day)?;
+
+    if let Some(example) = example {
+        let lib: PathBuf = target.join("src").join("lib.rs");
+        let content: String = fs::read_to_string(&lib).map_err(|err| format!("cannot read '{}': {}", lib.display(), err))?;
+        write(&lib, &content.replacen("const INPUT: &str = \"\";", &format!("const INPUT: &str = {};", string_literal(&example)), 1))?;
+    }
+
+    write(&manifest, &manifest_content)?;
+    write(&registry, &registry_content)?;
+
+    return Ok(target);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_sorted() {
+        let registry: &str = "pub static DAYS: &[Day] = &[\n    day!(2022, 1, a::Day01),\n    day!(2023, 10, a::Day10),\n];";
+
+        let result = insert_sorted(registry, "    day!(2023, 9, a::Day09),", registry_key).unwrap();
+        assert_eq!(result, "pub static DAYS: &[Day] = &[\n    day!(2022, 1, a::Day01),\n    day!(2023, 9, a::Day09),\n    day!(2023, 10, a::Day10),\n];");
+
+        let result = insert_sorted(registry, "    day!(2023, 11, a::Day11),", registry_key).unwrap();
+        assert_eq!(result, "pub static DAYS: &[Day] = &[\n    day!(2022, 1, a::Day01),\n    day!(2023, 10, a::Day10),\n    day!(2023, 11, a::Day11),\n];");
+
+        assert_eq!(insert_sorted(registry, "    day!(2022, 1, a::Day01),", registry_key), None);
+        assert_eq!(manifest_key("aoc2023-day-07 = { path = \"../2023/day-07\" }"), Some((2023, 7)));
+    }
+
+    #[test]
+    fn test_extract_example() {
+        let html: &str = "<p>For example:</p>\n<pre><code>#.##..##.\n..#<em>.</em>##.#.\n&lt;a&gt; \\ &amp;\n</code></pre>\n<pre><code>other</code></pre>";
+
+        let example: String = extract_example(html).unwrap();
+        assert_eq!(example, "#.##..##.\n..#.##.#.\n<a> \\ &");
+        assert_eq!(string_literal(&example), "\"#.##..##.\n..#.##.#.\n<a> \\\\ &\"");
+    }
+}
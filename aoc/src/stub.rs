@@ -0,0 +1,58 @@
+//! Local HTTP server standing in for the AoC website in tests.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+pub struct Stub {
+    pub base_url: String,
+    handle: JoinHandle<Vec<String>>,
+}
+
+impl Stub {
+    /// Waits until all responses are served and returns the received requests (head and body).
+    pub fn requests(self) -> Vec<String> {
+        return self.handle.join().unwrap();
+    }
+}
+
+/// Serves the given `(status, body)` responses in order, one per connection.
+pub fn serve(responses: Vec<(u16, &'static str)>) -> Stub {
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url: String = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = std::thread::spawn(move || {
+        return responses.iter().map(|(status, body)| {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader: BufReader<_> = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request: String = String::new();
+            let mut content_length: usize = 0;
+            loop {
+                let mut line: String = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+
+            let mut body_bytes: Vec<u8> = vec![0; content_length];
+            reader.read_exact(&mut body_bytes).unwrap();
+            request.push_str(&String::from_utf8(body_bytes).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {} STUB\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status, body.len(), body
+            ).unwrap();
+
+            return request;
+        }).collect();
+    });
+
+    return Stub { base_url, handle };
+}
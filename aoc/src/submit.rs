@@ -0,0 +1,264 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use common::Answer;
+use serde::{Deserialize, Serialize};
+
+use crate::answers::Answers;
+use crate::client::Client;
+use crate::registry::Day;
+
+/// Verdict of the website on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// answered too recently, the answer was not checked
+    Wait,
+    /// the part was already solved on the website
+    Solved,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return f.write_str(match self {
+            Outcome::Right => "That's the right answer!",
+            Outcome::Wrong => "That's not the right answer.",
+            Outcome::TooHigh => "That's not the right answer, it is too high.",
+            Outcome::TooLow => "That's not the right answer, it is too low.",
+            Outcome::Wait => "You gave an answer too recently.",
+            Outcome::Solved => "The part is already solved.",
+        });
+    }
+}
+
+/// Parses a wait like `one minute`, `5 minutes` or `4m 23s` into seconds.
+fn parse_wait(text: &str) -> Option<u64> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    for (idx, word) in words.iter().enumerate() {
+        let unit: &str = words.get(idx + 1).copied().unwrap_or("");
+        let count: Option<u64> = if *word == "one" { Some(1) } else { word.parse().ok() };
+        if let Some(count) = count {
+            if unit.starts_with("minute") {
+                return Some(count * 60);
+            }
+            if unit.starts_with("second") {
+                return Some(count);
+            }
+        }
+    }
+
+    // `You have 4m 23s left to wait`
+    let left: &str = text.split("You have ").nth(1)?.split(" left").next()?;
+    return left.split_whitespace().map(|it| {
+        let (value, unit) = it.split_at(it.len() - 1);
+        let value: u64 = value.parse().ok()?;
+        return match unit {
+            "h" => Some(value * 3600),
+            "m" => Some(value * 60),
+            "s" => Some(value),
+            _ => None,
+        };
+    }).sum();
+}
+
+/// Reads the verdict and the time to wait before the next answer from the response page.
+pub fn parse_response(html: &str) -> Result<(Outcome, u64), String> {
+    let text: &str = match (html.find("<article>"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+
+    let outcome: Outcome = if text.contains("That's the right answer") {
+        Outcome::Right
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::Wait
+    } else if text.contains("Did you already complete it") {
+        Outcome::Solved
+    } else if text.contains("your answer is too high") {
+        Outcome::TooHigh
+    } else if text.contains("your answer is too low") {
+        Outcome::TooLow
+    } else if text.contains("That's not the right answer") {
+        Outcome::Wrong
+    } else {
+        return Err(format!("unexpected response: {}", text.trim()));
+    };
+
+    let wait: u64 = match outcome {
+        Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow | Outcome::Wait => parse_wait(text).unwrap_or(60),
+        Outcome::Right | Outcome::Solved => 0,
+    };
+
+    return Ok((outcome, wait));
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Unix time of the submission
+    pub time: u64,
+    /// Unix time before which no other answer is accepted
+    pub retry_after: u64,
+}
+
+/// Every answer submitted for a single day, stored in `submissions.toml` next to its `answers.toml`.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "attempt")]
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<History, String> {
+        let content: String = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(History::default()),
+            Err(err) => return Err(format!("cannot read '{}': {}", path.display(), err)),
+        };
+
+        return toml::from_str(&content).map_err(|err| format!("invalid submissions file '{}': {}", path.display(), err));
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content: String = toml::to_string(self).unwrap();
+        return fs::write(path, content).map_err(|err| format!("cannot write '{}': {}", path.display(), err));
+    }
+
+    /// Explains why the answer must not be submitted, based on what was already learned about the part.
+    pub fn check(&self, part: u8, answer: &Answer, now: u64) -> Result<(), String> {
+        if let Some(last) = self.attempts.iter().max_by_key(|it| it.retry_after) {
+            if last.retry_after > now {
+                return Err(format!("wait {}s before submitting another answer", last.retry_after - now));
+            }
+        }
+
+        for attempt in self.attempts.iter().filter(|it| it.part == part) {
+            let submitted: Answer = Answer::from(attempt.answer.as_str());
+            if submitted == *answer && attempt.outcome != Outcome::Wait {
+                return Err(format!("'{}' was already submitted: {}", answer, attempt.outcome));
+            }
+
+            // only numbers are ordered, text answers are never too high or too low
+            match (attempt.outcome, answer.partial_cmp(&submitted)) {
+                (Outcome::TooHigh, Some(Ordering::Greater | Ordering::Equal)) => {
+                    return Err(format!("'{}' is too high, '{}' already was", answer, attempt.answer));
+                }
+                (Outcome::TooLow, Some(Ordering::Less | Ordering::Equal)) => {
+                    return Err(format!("'{}' is too low, '{}' already was", answer, attempt.answer));
+                }
+                _ => {}
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+fn now() -> u64 {
+    return SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+}
+
+/// Submits the answer unless it is known to be wrong, records the attempt and stores a right answer.
+pub fn submit(client: &Client, day: &Day, data_dir: &Path, part: u8, answer: &Answer) -> Result<Outcome, String> {
+    return submit_at(client, day.year, day.day, &day.dir(data_dir), part, answer, now());
+}
+
+fn submit_at(client: &Client, year: u16, day: u8, dir: &Path, part: u8, answer: &Answer, now: u64) -> Result<Outcome, String> {
+    let answers_path: PathBuf = dir.join("answers.toml");
+    let history_path: PathBuf = dir.join("submissions.toml");
+
+    let mut answers: Answers = Answers::load(&answers_path)?;
+    if let Some(accepted) = answers.get(part) {
+        let accepted: Answer = Answer::from(accepted.as_str());
+        return if accepted == *answer {
+            Ok(Outcome::Solved)
+        } else {
+            Err(format!("'{}' was already accepted as the answer", accepted))
+        };
+    }
+
+    let mut history: History = History::load(&history_path)?;
+    history.check(part, answer, now)?;
+
+    let answer: String = answer.to_string();
+    let response: String = client.submit_answer(year, day, part, &answer).map_err(|err| err.to_string())?;
+    let (outcome, wait) = parse_response(&response)?;
+
+    history.attempts.push(Attempt { part, answer: answer.clone(), outcome, time: now, retry_after: now + wait });
+    history.save(&history_path)?;
+
+    if outcome == Outcome::Right {
+        answers.set(part, &answer);
+        answers.save(&answers_path)?;
+    }
+
+    return Ok(outcome);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stub;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let wrong: &str = "<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, \
+            make sure you're using the full input data. Please wait one minute before trying again. \
+            <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article></main>";
+        assert_eq!(parse_response(wrong), Ok((Outcome::TooHigh, 60)));
+
+        let wait: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer \
+            before trying again.  You have 4m 23s left to wait.</p></article>";
+        assert_eq!(parse_response(wait), Ok((Outcome::Wait, 263)));
+
+        let wrong: &str = "<article><p>That's not the right answer.  Please wait 5 minutes before trying again.</p></article>";
+        assert_eq!(parse_response(wrong), Ok((Outcome::Wrong, 300)));
+
+        assert_eq!(parse_response("<article><p>That's the right answer!  You are one gold star closer.</p></article>"), Ok((Outcome::Right, 0)));
+        assert_eq!(
+            parse_response("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>"),
+            Ok((Outcome::Solved, 0))
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let dir: PathBuf = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let server = stub::serve(vec![
+            (200, "<article><p>That's not the right answer; your answer is too high.  Please wait one minute before trying again.</p></article>"),
+            (200, "<article><p>That's the right answer!  You are one gold star closer.</p></article>"),
+        ]);
+        let client: Client = Client::create(&server.base_url, "session=abc");
+
+        assert_eq!(submit_at(&client, 2023, 1, &dir, 1, &Answer::from(100), 0), Ok(Outcome::TooHigh));
+        // refused without asking the website
+        assert!(submit_at(&client, 2023, 1, &dir, 1, &Answer::from(50), 30).unwrap_err().starts_with("wait 30s"));
+        assert!(submit_at(&client, 2023, 1, &dir, 1, &Answer::from(100), 100).is_err());
+        assert!(submit_at(&client, 2023, 1, &dir, 1, &Answer::from(150), 100).is_err());
+
+        assert_eq!(submit_at(&client, 2023, 1, &dir, 1, &Answer::from(50), 100), Ok(Outcome::Right));
+        assert_eq!(Answers::load(&dir.join("answers.toml")).unwrap().get(1), Some(&"50".to_string()));
+        assert_eq!(History::load(&dir.join("submissions.toml")).unwrap().attempts.len(), 2);
+        assert_eq!(submit_at(&client, 2023, 1, &dir, 1, &Answer::from(50), 200), Ok(Outcome::Solved));
+
+        let requests: Vec<String> = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("POST /2023/day/1/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=1&answer=100"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
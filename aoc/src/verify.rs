@@ -0,0 +1,110 @@
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::process::ExitCode;
+
+use common::{input, Answer};
+
+use crate::answers::Answers;
+use crate::registry::Day;
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return f.pad(match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+        });
+    }
+}
+
+fn check(answer: &Answer, expected: Option<&Answer>) -> Status {
+    return match expected {
+        Some(expected) if expected == answer => Status::Pass,
+        Some(_) => Status::Fail,
+        None => Status::Missing,
+    };
+}
+
+fn print_row(cells: [&str; 6]) {
+    let [year, day, part, status, answer, expected] = cells;
+    let row: String = format!("{:<6}{:<5}{:<6}{:<9}{:<20}{}", year, day, part, status, answer, expected);
+    println!("{}", row.trim_end());
+}
+
+/// Runs every given day against its stored answers and prints a pass/fail/missing table.
+pub fn verify(days: &[&Day], data_dir: &Path) -> ExitCode {
+    let mut counts: [usize; 3] = [0, 0, 0];
+
+    print_row(["year", "day", "part", "status", "answer", "expected"]);
+
+    for day in days {
+        let answers: Answers = match Answers::load(&Answers::path(day, data_dir)) {
+            Ok(answers) => answers,
+            Err(err) => {
+                eprintln!("{} day {:02}: {}", day.year, day.day, err);
+                counts[1] += day.parts().len();
+                continue;
+            }
+        };
+
+        let input: Option<String> = input::load(&day.input_path(data_dir)).ok();
+
+        for (part, solve) in day.parts() {
+            let expected: Option<Answer> = answers.get(part).map(|it| Answer::from(it.as_str()));
+
+            let (status, answer) = match &input {
+                Some(input) => match solve(input) {
+                    Ok(answer) => (check(&answer, expected.as_ref()), answer.to_string()),
+                    Err(err) => {
+                        eprintln!("{} day {:02} part {}: {}", day.year, day.day, part, err);
+                        (Status::Fail, "<error>".to_string())
+                    }
+                },
+                // without input there is nothing to compare with
+                None => (Status::Missing, "<no input>".to_string()),
+            };
+
+            let idx: usize = match status {
+                Status::Pass => 0,
+                Status::Fail => 1,
+                Status::Missing => 2,
+            };
+            counts[idx] += 1;
+
+            let expected: String = if status == Status::Fail { expected.map_or(String::new(), |it| it.to_string()) } else { String::new() };
+            print_row([
+                &day.year.to_string(),
+                &format!("{:02}", day.day),
+                &part.to_string(),
+                &status.to_string(),
+                &answer,
+                &expected,
+            ]);
+        }
+    }
+
+    println!();
+    println!("passed: {}, failed: {}, missing: {}", counts[0], counts[1], counts[2]);
+
+    return if counts[1] > 0 { ExitCode::FAILURE } else { ExitCode::SUCCESS };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert_eq!(check(&Answer::from(54968), Some(&Answer::from("54968"))), Status::Pass);
+        assert_eq!(check(&Answer::from(54967), Some(&Answer::from("54968"))), Status::Fail);
+        assert_eq!(check(&Answer::from("CMZ"), Some(&Answer::from("CMZ\n"))), Status::Pass);
+        assert_eq!(check(&Answer::from(54968), None), Status::Missing);
+    }
+}
@@ -0,0 +1,162 @@
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+/// Answer of a puzzle part.
+///
+/// Answers compare equal regardless of how they are written: numbers by value, text without surrounding
+/// whitespace, and ASCII art by its rows without trailing whitespace and blank rows around it.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Int(i128),
+    /// Number which does not fit into [`Answer::Int`]
+    BigInt(BigInt),
+    Text(String),
+    /// Letters drawn on multiple rows, e.g. by lit pixels of a screen
+    Art(Vec<String>),
+}
+
+impl Answer {
+    fn number(&self) -> Option<BigInt> {
+        return match self {
+            Answer::Int(value) => Some(BigInt::from(*value)),
+            Answer::BigInt(value) => Some(value.clone()),
+            _ => None,
+        };
+    }
+
+    fn rows(&self) -> Vec<&str> {
+        let rows: Vec<&str> = match self {
+            Answer::Int(_) | Answer::BigInt(_) => return Vec::new(),
+            Answer::Text(text) => vec![text.trim()],
+            Answer::Art(rows) => rows.iter().map(|it| it.trim_end()).collect(),
+        };
+        let first: usize = rows.iter().position(|it| !it.is_empty()).unwrap_or(rows.len());
+        let last: usize = rows.iter().rposition(|it| !it.is_empty()).map_or(first, |it| it + 1);
+        return rows[first..last].to_vec();
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::BigInt(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Art(rows) => write!(f, "{}", rows.join("\n")),
+        };
+    }
+}
+
+/// Reads numbers, single-line text and multi-line ASCII art, every string is a valid answer.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed: &str = s.trim();
+        if let Ok(value) = trimmed.parse::<i128>() {
+            return Ok(Answer::Int(value));
+        }
+        if let Ok(value) = trimmed.parse::<BigInt>() {
+            return Ok(Answer::BigInt(value));
+        }
+        if trimmed.contains('\n') {
+            return Ok(Answer::Art(s.lines().map(|it| it.to_string()).collect()));
+        }
+        return Ok(Answer::Text(trimmed.to_string()));
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        return match (self.number(), other.number()) {
+            (Some(left), Some(right)) => left == right,
+            (None, None) => self.rows() == other.rows(),
+            _ => false,
+        };
+    }
+}
+
+impl Eq for Answer {}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        let other: Answer = Answer::from(*other);
+        return *self == other;
+    }
+}
+
+/// Numbers are ordered by value, other answers are only equal or not.
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return match (self.number(), other.number()) {
+            (Some(left), Some(right)) => Some(left.cmp(&right)),
+            _ if self == other => Some(Ordering::Equal),
+            _ => None,
+        };
+    }
+}
+
+macro_rules! from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(value: $int) -> Self {
+                    return Answer::Int(value as i128);
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i32, i64, i128, u8, u16, u32, u64, usize);
+
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Self {
+        return match value.to_i128() {
+            Some(value) => Answer::Int(value),
+            None => Answer::BigInt(value),
+        };
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        let Ok(answer) = value.parse();
+        return answer;
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        return Answer::from(value.as_str());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(" 0042\n".parse::<Answer>().unwrap(), Answer::Int(42));
+        assert!(matches!("123456789012345678901234567890123456789012".parse::<Answer>().unwrap(), Answer::BigInt(_)));
+        assert!(matches!("CMZ".parse::<Answer>().unwrap(), Answer::Text(text) if text == "CMZ"));
+        assert_eq!(Answer::from("#..#\n####\n#..#").to_string(), "#..#\n####\n#..#");
+    }
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(Answer::from(BigInt::from(167409079868000u64)), Answer::Int(167409079868000));
+        assert_eq!(Answer::BigInt(BigInt::from(7)), Answer::from(7u8));
+        assert_eq!(Answer::Text(" MCD ".to_string()), "MCD");
+        assert_eq!(Answer::from("\n#..#  \n####\n\n"), Answer::Art(vec!["#..#".to_string(), "####".to_string()]));
+        assert_ne!(Answer::from(12), "12a");
+        assert!(Answer::from(12) < Answer::BigInt(BigInt::from(100)));
+        assert_eq!(Answer::from("abc").partial_cmp(&Answer::from("abd")), None);
+    }
+}
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use crate::input;
+use crate::report::{self, Format, Record, Status, Timed};
+use crate::Solution;
+
+#[derive(Parser)]
+#[clap(author, version, about, long_about = None)]
+pub struct Cli {
+    /// Directory with the `input.txt` of the puzzle
+    #[clap(short, long, value_parser, default_value = "./")]
+    pub data_dir: PathBuf,
+    /// Read the puzzle input from the given file, `-` reads it from stdin
+    #[clap(short, long, value_parser, conflicts_with = "example")]
+    pub input: Option<PathBuf>,
+    /// Solve the example from the puzzle description instead of the puzzle input
+    #[clap(short, long)]
+    pub example: bool,
+    /// Print the answer as text, or as a JSON record with timings and status
+    #[clap(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+impl Cli {
+    pub fn input_path(&self) -> PathBuf {
+        return match &self.input {
+            Some(path) => path.clone(),
+            None => self.data_dir.join("input.txt"),
+        };
+    }
+}
+
+/// Entry point of the `partNN` binaries, solves a single part of the puzzle and prints the answer.
+///
+/// Errors are printed to stderr, or as part of the record with `--format json`; the exit code tells which kind of
+/// error it was.
+pub fn run<S: Solution>(year: u16, day: u8, part: u8) -> ExitCode {
+    let args: Cli = Cli::parse();
+
+    let timed: Option<Timed> = if args.example {
+        report::solve_timed::<S>(part, None)
+    } else {
+        match input::load(&args.input_path()) {
+            Ok(input) => report::solve_timed::<S>(part, Some(&input)),
+            Err(err) => {
+                print_failure(args.format, Record::failed(year, day, part, Status::Failed, Some(err.to_string())));
+                return ExitCode::FAILURE;
+            }
+        }
+    };
+
+    let code: ExitCode = match timed.as_ref().map(|it| &it.answer) {
+        Some(Ok(_)) => ExitCode::SUCCESS,
+        Some(Err(err)) => ExitCode::from(err.exit_code()),
+        None => ExitCode::FAILURE,
+    };
+
+    let record: Record = Record::create(year, day, part, timed.as_ref());
+    match args.format {
+        Format::Text => match timed.map(|it| it.answer) {
+            Some(Ok(answer)) => println!("{}", answer),
+            Some(Err(err)) => eprintln!("{}", err),
+            None => eprintln!("Puzzle has no example for part {}", part),
+        },
+        Format::Json => println!("{}", report::to_document(&[record])),
+        Format::Ndjson => println!("{}", record.to_line()),
+    }
+
+    return code;
+}
+
+fn print_failure(format: Format, record: Record) {
+    match format {
+        Format::Text => eprintln!("{}", record.error.unwrap_or_default()),
+        Format::Json => println!("{}", report::to_document(&[record])),
+        Format::Ndjson => println!("{}", record.to_line()),
+    }
+}
@@ -0,0 +1,157 @@
+//! Cycle detection in sequences of states `x, step(x), step(step(x)), ...`.
+//!
+//! Puzzles asking for the state after a billion steps usually repeat after a short prefix, so the state at step `n`
+//! is the state at the step with the same position in the cycle.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Cycle of a sequence of states, the state at step `start + period` is equal to the state at step `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Number of steps before the first state of the cycle
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// First step with the same state as step `n`.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            return n;
+        }
+        return self.start + (n - self.start) % self.period;
+    }
+}
+
+/// Finds the cycle with Floyd's tortoise and hare, keeping only two states in memory.
+pub fn floyd<T: Clone + PartialEq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise: T = step(&initial);
+    let mut hare: T = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next: T = step(&hare);
+        hare = step(&next);
+    }
+
+    let mut start: usize = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut period: usize = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+
+    return Cycle { start, period };
+}
+
+/// Finds the cycle with Brent's algorithm, which needs fewer steps than [`floyd`].
+pub fn brent<T: Clone + PartialEq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    let mut power: usize = 1;
+    let mut period: usize = 1;
+    let mut tortoise: T = initial.clone();
+    let mut hare: T = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+
+    let mut start: usize = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    return Cycle { start, period };
+}
+
+/// Finds the cycle by remembering every state, returns the states before the repeated one in order.
+///
+/// Every state is computed once, so it is the fastest detection for expensive steps.
+pub fn find<T: Clone + Eq + Hash>(initial: T, mut step: impl FnMut(&T) -> T) -> (Cycle, Vec<T>) {
+    let mut seen: HashMap<T, usize> = HashMap::new();
+    let mut states: Vec<T> = Vec::new();
+    let mut state: T = initial;
+
+    loop {
+        if let Some(start) = seen.get(&state) {
+            return (Cycle { start: *start, period: states.len() - start }, states);
+        }
+        let next: T = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// State after `n` steps, skipping the repeated cycles.
+pub fn state_at<T: Clone + Eq + Hash>(initial: T, mut step: impl FnMut(&T) -> T, n: usize) -> T {
+    let mut seen: HashMap<T, usize> = HashMap::new();
+    let mut states: Vec<T> = Vec::new();
+    let mut state: T = initial;
+
+    while states.len() < n {
+        if let Some(start) = seen.get(&state) {
+            let cycle: Cycle = Cycle { start: *start, period: states.len() - start };
+            return states.swap_remove(cycle.index(n));
+        }
+        let next: T = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+
+    return state;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4, 5, 6, 2, 3, ...
+    fn step(state: &u32) -> u32 {
+        return if *state == 6 { 2 } else { state + 1 };
+    }
+
+    #[test]
+    fn test_detection() {
+        let expected: Cycle = Cycle { start: 2, period: 5 };
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+
+        let (cycle, states) = find(0, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(states, vec![0, 1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(floyd(2, step), Cycle { start: 0, period: 5 });
+        assert_eq!(brent(7, |_| 7), Cycle { start: 0, period: 1 });
+    }
+
+    #[test]
+    fn test_state_at() {
+        assert_eq!(Cycle { start: 2, period: 5 }.index(1), 1);
+        assert_eq!(Cycle { start: 2, period: 5 }.index(12), 2);
+        assert_eq!(state_at(0, step, 1), 1);
+        assert_eq!(state_at(0, step, 7), 2);
+        assert_eq!(state_at(0, step, 1_000_000_000), 5);
+    }
+}
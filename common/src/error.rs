@@ -0,0 +1,64 @@
+use std::fmt::{Display, Formatter};
+
+use crate::parse::ParseError;
+
+/// Reasons a puzzle part has no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// Puzzle input which cannot be parsed
+    Parse(ParseError),
+    /// Valid input without an answer, e.g. an unreachable target
+    NoSolution(String),
+    /// Puzzle parameter out of its supported range
+    InvalidParameter(String),
+}
+
+impl SolveError {
+    /// Exit code of the binaries for the error, following `sysexits.h` where it has a matching code.
+    pub fn exit_code(&self) -> u8 {
+        return match self {
+            SolveError::Parse(_) => 65,
+            SolveError::NoSolution(_) => 1,
+            SolveError::InvalidParameter(_) => 64,
+        };
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            SolveError::Parse(err) => write!(f, "invalid puzzle input at {}", err),
+            SolveError::NoSolution(reason) => write!(f, "no solution: {}", reason),
+            SolveError::InvalidParameter(reason) => write!(f, "invalid parameter: {}", reason),
+        };
+    }
+}
+
+impl std::error::Error for SolveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            SolveError::Parse(err) => Some(err),
+            _ => None,
+        };
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(err: ParseError) -> Self {
+        return SolveError::Parse(err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let input: &str = "ab\ncd";
+        let err: SolveError = ParseError::at(input, &input[4..], "unexpected 'd'").into();
+        assert_eq!(err.to_string(), "invalid puzzle input at line 2, column 2: unexpected 'd'\n    cd\n     ^");
+        assert_eq!(err.exit_code(), 65);
+        assert_eq!(SolveError::NoSolution("target is unreachable".to_string()).to_string(), "no solution: target is unreachable");
+    }
+}
@@ -0,0 +1,293 @@
+//! Integer coordinates and directions.
+//!
+//! 2D positions follow the [`Grid`](crate::grid::Grid) convention: `row` grows down and `col` grows right,
+//! so [`Direction::Up`] decreases the row.
+
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::math::gcd;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl Position {
+    pub const ORIGIN: Position = Position { row: 0, col: 0 };
+
+    pub fn create(row: i64, col: i64) -> Position {
+        return Position { row, col };
+    }
+
+    pub fn step(&self, direction: Direction) -> Position {
+        return *self + direction.offset();
+    }
+
+    pub fn manhattan(&self, other: &Position) -> i64 {
+        return (self.row - other.row).abs() + (self.col - other.col).abs();
+    }
+
+    pub fn chebyshev(&self, other: &Position) -> i64 {
+        return (self.row - other.row).abs().max((self.col - other.col).abs());
+    }
+
+    /// Orthogonal neighbours, clockwise from the upper one.
+    pub fn neighbours4(&self) -> impl Iterator<Item = Position> + '_ {
+        return Direction::ALL.iter().map(|it| self.step(*it));
+    }
+
+    /// Orthogonal and diagonal neighbours, clockwise from the upper one.
+    pub fn neighbours8(&self) -> impl Iterator<Item = Position> + '_ {
+        return Direction::ALL.iter()
+            .zip(Diagonal::ALL.iter())
+            .flat_map(|(direction, diagonal)| [*self + direction.offset(), *self + diagonal.offset()]);
+    }
+}
+
+impl From<(usize, usize)> for Position {
+    fn from((row, col): (usize, usize)) -> Self {
+        return Position { row: row as i64, col: col as i64 };
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "({}, {})", self.row, self.col);
+    }
+}
+
+impl Add for Position {
+    type Output = Position;
+
+    fn add(self, rhs: Position) -> Self::Output {
+        return Position { row: self.row + rhs.row, col: self.col + rhs.col };
+    }
+}
+
+impl AddAssign for Position {
+    fn add_assign(&mut self, rhs: Position) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Position {
+    type Output = Position;
+
+    fn sub(self, rhs: Position) -> Self::Output {
+        return Position { row: self.row - rhs.row, col: self.col - rhs.col };
+    }
+}
+
+impl SubAssign for Position {
+    fn sub_assign(&mut self, rhs: Position) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Position {
+    type Output = Position;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        return Position { row: self.row * rhs, col: self.col * rhs };
+    }
+}
+
+impl Neg for Position {
+    type Output = Position;
+
+    fn neg(self) -> Self::Output {
+        return Position { row: -self.row, col: -self.col };
+    }
+}
+
+impl Add<Direction> for Position {
+    type Output = Position;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        return self + rhs.offset();
+    }
+}
+
+/// Twice the area of the polygon with the vertices in order, by the shoelace formula.
+///
+/// Twice the area of a polygon with integer vertices is always an integer.
+pub fn double_area(vertices: &[Position]) -> i64 {
+    let sum: i128 = vertices.iter().zip(vertices.iter().cycle().skip(1)).map(|(a, b)| {
+        return a.row as i128 * b.col as i128 - b.row as i128 * a.col as i128;
+    }).sum();
+    return sum.abs() as i64;
+}
+
+/// Number of lattice points on the edges of the polygon.
+pub fn boundary_count(vertices: &[Position]) -> i64 {
+    return vertices.iter().zip(vertices.iter().cycle().skip(1)).map(|(a, b)| {
+        return gcd(a.row - b.row, a.col - b.col) as i64;
+    }).sum();
+}
+
+/// Number of lattice points strictly inside the polygon, by Pick's theorem `A = I + B / 2 - 1`.
+pub fn interior_count(vertices: &[Position]) -> i64 {
+    return (double_area(vertices) - boundary_count(vertices) + 2) / 2;
+}
+
+/// Cardinal direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    pub fn offset(&self) -> Position {
+        return match self {
+            Direction::Up => Position { row: -1, col: 0 },
+            Direction::Right => Position { row: 0, col: 1 },
+            Direction::Down => Position { row: 1, col: 0 },
+            Direction::Left => Position { row: 0, col: -1 },
+        };
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        return match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        };
+    }
+
+    pub fn turn_left(&self) -> Direction {
+        return match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        };
+    }
+
+    pub fn reverse(&self) -> Direction {
+        return match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        };
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        return matches!(self, Direction::Up | Direction::Down);
+    }
+}
+
+/// Diagonal direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Diagonal {
+    UpRight,
+    DownRight,
+    DownLeft,
+    UpLeft,
+}
+
+impl Diagonal {
+    /// Clockwise from `UpRight`.
+    pub const ALL: [Diagonal; 4] = [Diagonal::UpRight, Diagonal::DownRight, Diagonal::DownLeft, Diagonal::UpLeft];
+
+    pub fn offset(&self) -> Position {
+        return match self {
+            Diagonal::UpRight => Position { row: -1, col: 1 },
+            Diagonal::DownRight => Position { row: 1, col: 1 },
+            Diagonal::DownLeft => Position { row: 1, col: -1 },
+            Diagonal::UpLeft => Position { row: -1, col: -1 },
+        };
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub fn create(x: i64, y: i64, z: i64) -> Point3 {
+        return Point3 { x, y, z };
+    }
+
+    pub fn manhattan(&self, other: &Point3) -> i64 {
+        return (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs();
+    }
+
+    pub fn chebyshev(&self, other: &Point3) -> i64 {
+        return (self.x - other.x).abs().max((self.y - other.y).abs()).max((self.z - other.z).abs());
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, rhs: Point3) -> Self::Output {
+        return Point3 { x: self.x + rhs.x, y: self.y + rhs.y, z: self.z + rhs.z };
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, rhs: Point3) -> Self::Output {
+        return Point3 { x: self.x - rhs.x, y: self.y - rhs.y, z: self.z - rhs.z };
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        return Point3 { x: self.x * rhs, y: self.y * rhs, z: self.z * rhs };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let position: Position = Position::create(2, 3);
+        assert_eq!(position.step(Direction::Up), Position::create(1, 3));
+        assert_eq!(position + Direction::Left, Position::create(2, 2));
+        assert_eq!(position * 2 - Position::create(1, 1), Position::create(3, 5));
+        assert_eq!(-position, Position::create(-2, -3));
+        assert_eq!(position.manhattan(&Position::ORIGIN), 5);
+        assert_eq!(position.chebyshev(&Position::ORIGIN), 3);
+        assert_eq!(position.neighbours8().count(), 8);
+        assert_eq!(Point3::create(1, -2, 3).manhattan(&Point3::default()), 6);
+    }
+
+    #[test]
+    fn test_polygon() {
+        let square: Vec<Position> = vec![Position::create(0, 0), Position::create(0, 4), Position::create(4, 4), Position::create(4, 0)];
+        assert_eq!((double_area(&square), boundary_count(&square), interior_count(&square)), (32, 16, 9));
+
+        let triangle: Vec<Position> = vec![Position::create(0, 0), Position::create(3, 3), Position::create(0, 6)];
+        assert_eq!((double_area(&triangle), boundary_count(&triangle), interior_count(&triangle)), (18, 12, 4));
+    }
+
+    #[test]
+    fn test_direction() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(direction.offset() + direction.reverse().offset(), Position::ORIGIN);
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert!(Direction::Down.is_vertical());
+    }
+}
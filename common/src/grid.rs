@@ -0,0 +1,333 @@
+//! Rectangular 2D grid of cells, the usual shape of AoC puzzle inputs.
+//!
+//! Cells are addressed by `(row, col)`, row `0` being the first line of the input.
+
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::geom::Position;
+
+/// Offsets `(row, col)` of the 4 orthogonal neighbours: up, right, down, left.
+pub const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets `(row, col)` of all 8 neighbours, clockwise from the upper left one.
+pub const NEIGHBOURS8: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1)];
+
+#[derive(Debug, PartialEq)]
+pub enum GridError {
+    Empty,
+    /// row (1-based) with a different length than the first one
+    Ragged { row: usize, expected: usize, actual: usize },
+    /// char at row and column (both 1-based) with no cell value
+    Cell { row: usize, col: usize, char: char },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            GridError::Empty => write!(f, "grid is empty"),
+            GridError::Ragged { row, expected, actual } => {
+                write!(f, "row {} has {} cells, expected {}", row, actual, expected)
+            }
+            GridError::Cell { row, col, char } => write!(f, "unexpected '{}' at {}:{}", char, row, col),
+        };
+    }
+}
+
+impl std::error::Error for GridError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parses lines of chars, mapping every char to a cell.
+    pub fn parse_with(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, GridError> {
+        let mut width: usize = 0;
+        let mut height: usize = 0;
+        let mut cells: Vec<T> = Vec::new();
+
+        for (row, line) in input.lines().enumerate() {
+            let before: usize = cells.len();
+            for (col, char) in line.chars().enumerate() {
+                cells.push(cell(char).ok_or(GridError::Cell { row: row + 1, col: col + 1, char })?);
+            }
+
+            let actual: usize = cells.len() - before;
+            if row == 0 {
+                width = actual;
+            } else if actual != width {
+                return Err(GridError::Ragged { row: row + 1, expected: width, actual });
+            }
+            height += 1;
+        }
+
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+
+        return Ok(Grid { width, height, cells });
+    }
+
+    /// Builds the grid from rows of equal length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, GridError> {
+        let width: usize = rows.first().map_or(0, |it| it.len());
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+
+        let height: usize = rows.len();
+        let mut cells: Vec<T> = Vec::with_capacity(width * height);
+        for (row, values) in rows.into_iter().enumerate() {
+            if values.len() != width {
+                return Err(GridError::Ragged { row: row + 1, expected: width, actual: values.len() });
+            }
+            cells.extend(values);
+        }
+
+        return Ok(Grid { width, height, cells });
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub fn contains(&self, row: isize, col: isize) -> bool {
+        return row >= 0 && col >= 0 && (row as usize) < self.height && (col as usize) < self.width;
+    }
+
+    pub fn get(&self, row: isize, col: isize) -> Option<&T> {
+        if !self.contains(row, col) {
+            return None;
+        }
+        return Some(&self.cells[row as usize * self.width + col as usize]);
+    }
+
+    pub fn get_mut(&mut self, row: isize, col: isize) -> Option<&mut T> {
+        if !self.contains(row, col) {
+            return None;
+        }
+        return Some(&mut self.cells[row as usize * self.width + col as usize]);
+    }
+
+    pub fn is_inside(&self, position: Position) -> bool {
+        return self.contains(position.row as isize, position.col as isize);
+    }
+
+    pub fn at(&self, position: Position) -> Option<&T> {
+        return self.get(position.row as isize, position.col as isize);
+    }
+
+    pub fn at_mut(&mut self, position: Position) -> Option<&mut T> {
+        return self.get_mut(position.row as isize, position.col as isize);
+    }
+
+    /// Cell of the grid repeated infinitely in every direction.
+    pub fn get_wrapping(&self, row: isize, col: isize) -> &T {
+        let row: usize = row.rem_euclid(self.height as isize) as usize;
+        let col: usize = col.rem_euclid(self.width as isize) as usize;
+        return &self.cells[row * self.width + col];
+    }
+
+    /// Cell next to `(row, col)` in the direction of the `(row, col)` offset, if it is inside the grid.
+    pub fn step(&self, (row, col): (usize, usize), (d_row, d_col): (isize, isize)) -> Option<(usize, usize)> {
+        let (row, col) = (row as isize + d_row, col as isize + d_col);
+        return if self.contains(row, col) { Some((row as usize, col as usize)) } else { None };
+    }
+
+    /// Orthogonal neighbours inside the grid.
+    pub fn neighbours4(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        return NEIGHBOURS4.iter().filter_map(move |offset| self.step(position, *offset));
+    }
+
+    /// Orthogonal and diagonal neighbours inside the grid.
+    pub fn neighbours8(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        return NEIGHBOURS8.iter().filter_map(move |offset| self.step(position, *offset));
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        return &self.cells[row * self.width..(row + 1) * self.width];
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        return self.cells.chunks(self.width);
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        return self.cells.iter().skip(col).step_by(self.width);
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        return (0..self.width).map(|col| self.column(col));
+    }
+
+    /// Every cell with its `(row, col)`, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        return self.cells.iter().enumerate().map(|(idx, it)| ((idx / self.width, idx % self.width), it));
+    }
+
+    /// Every position of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width: usize = self.width;
+        return (0..self.cells.len()).map(move |idx| Position::from((idx / width, idx % width)));
+    }
+
+    pub fn cells(&self) -> &[T] {
+        return &self.cells;
+    }
+
+    /// Position of the first cell, row by row, matching the predicate.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        return self.cells.iter().position(predicate).map(|idx| (idx / self.width, idx % self.width));
+    }
+
+    /// Positions of all cells matching the predicate.
+    pub fn find_all<'a>(&'a self, predicate: impl Fn(&T) -> bool + 'a) -> impl Iterator<Item = (usize, usize)> + 'a {
+        return self.iter().filter(move |(_, it)| predicate(it)).map(|(position, _)| position);
+    }
+
+    pub fn swap(&mut self, (row1, col1): (usize, usize), (row2, col2): (usize, usize)) {
+        self.cells.swap(row1 * self.width + col1, row2 * self.width + col2);
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        return Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() };
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn create(width: usize, height: usize, value: T) -> Grid<T> {
+        return Grid { width, height, cells: vec![value; width * height] };
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Grid<T> {
+        let cells: Vec<T> = (0..self.width).flat_map(|col| self.column(col).cloned()).collect();
+        return Grid { width: self.height, height: self.width, cells };
+    }
+
+    /// Rotates by 90 degrees clockwise, the first column becomes the first row.
+    pub fn rotate_right(&self) -> Grid<T> {
+        let cells: Vec<T> = (0..self.width).flat_map(|col| self.column(col).rev().cloned()).collect();
+        return Grid { width: self.height, height: self.width, cells };
+    }
+
+    /// Rotates by 90 degrees counterclockwise, the last column becomes the first row.
+    pub fn rotate_left(&self) -> Grid<T> {
+        let cells: Vec<T> = (0..self.width).rev().flat_map(|col| self.column(col).cloned()).collect();
+        return Grid { width: self.height, height: self.width, cells };
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn position_of(&self, value: &T) -> Option<(usize, usize)> {
+        return self.find(|it| it == value);
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(row < self.height && col < self.width, "({}, {}) is outside of {}x{} grid", row, col, self.height, self.width);
+        return &self.cells[row * self.width + col];
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(row < self.height && col < self.width, "({}, {}) is outside of {}x{} grid", row, col, self.height, self.width);
+        return &mut self.cells[row * self.width + col];
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        return self.at(position).unwrap_or_else(|| panic!("{} is outside of {}x{} grid", position, self.height, self.width));
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        let (height, width) = (self.height, self.width);
+        return self.at_mut(position).unwrap_or_else(|| panic!("{} is outside of {}x{} grid", position, height, width));
+    }
+}
+
+/// Parses a grid of any cell which can be created from a char, e.g. `char` itself.
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Grid::parse_with(s, |it| T::try_from(it).ok());
+    }
+}
+
+/// Prints the grid back as lines of cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geom::Direction;
+
+    use super::*;
+
+    const INPUT: &str = "#.#
+..#";
+
+    #[test]
+    fn test_parse() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], '#');
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get_wrapping(-1, 4), &'.');
+        assert_eq!(grid.to_string(), INPUT);
+
+        let digits: Grid<u32> = Grid::parse_with("12\n34", |it| it.to_digit(10)).unwrap();
+        assert_eq!(digits.row(1), &[3, 4]);
+        assert_eq!("12\n3".parse::<Grid<char>>(), Err(GridError::Ragged { row: 2, expected: 2, actual: 1 }));
+        assert_eq!(Grid::parse_with("12\n3x", |it| it.to_digit(10)), Err(GridError::Cell { row: 2, col: 2, char: 'x' }));
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!(grid.transpose().to_string(), "#.\n..\n##");
+        assert_eq!(grid.rotate_right().to_string(), ".#\n..\n##");
+        assert_eq!(grid.rotate_left().to_string(), "##\n..\n#.");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+
+        assert_eq!(grid.neighbours4((0, 1)).collect::<Vec<_>>(), vec![(0, 2), (1, 1), (0, 0)]);
+        assert_eq!(grid.neighbours8((1, 0)).count(), 3);
+        assert_eq!(grid.find_all(|it| *it == '#').collect::<Vec<_>>(), vec![(0, 0), (0, 2), (1, 2)]);
+        assert_eq!(grid.column(2).collect::<String>(), "##");
+
+        let position: Position = Position::create(1, 2);
+        assert_eq!(grid[position], '#');
+        assert_eq!(grid.at(position + Direction::Right), None);
+        assert_eq!(grid.positions().filter(|it| grid[*it] == '#').count(), 3);
+    }
+}
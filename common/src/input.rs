@@ -0,0 +1,88 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// Path which makes the loader read the puzzle input from stdin.
+pub const STDIN: &str = "-";
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing(PathBuf),
+    Unreadable(PathBuf, io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            InputError::Missing(path) => write!(
+                f,
+                "input file '{}' does not exist, download the puzzle input first or pass `--input <file>`",
+                path.display()
+            ),
+            InputError::Unreadable(path, err) => write!(f, "cannot read input '{}': {}", path.display(), err),
+        };
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Reads the puzzle input from the given file, or from stdin when the path is `-`, normalised by [`normalize`].
+pub fn load(path: &Path) -> Result<String, InputError> {
+    let input: String = if path.as_os_str() == STDIN {
+        let mut input: String = String::new();
+        match io::stdin().read_to_string(&mut input) {
+            Ok(_) => input,
+            Err(err) => return Err(InputError::Unreadable(path.to_path_buf(), err)),
+        }
+    } else {
+        fs::read_to_string(path).map_err(|err| match err.kind() {
+            ErrorKind::NotFound => InputError::Missing(path.to_path_buf()),
+            _ => InputError::Unreadable(path.to_path_buf(), err),
+        })?
+    };
+
+    return Ok(normalize(&input).into_owned());
+}
+
+/// Input with `\n` line endings, without a byte order mark and without trailing whitespace on any line or at its end.
+///
+/// Leading whitespace is kept, some inputs are aligned by it.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input: &str = input.strip_prefix('\u{feff}').unwrap_or(input).trim_end();
+    if !input.contains('\r') && input.lines().all(|it| it.len() == it.trim_end().len()) {
+        return Cow::Borrowed(input);
+    }
+    return Cow::Owned(input.lines().map(str::trim_end).collect::<Vec<&str>>().join("\n"));
+}
+
+/// Variants of the input as saved by different editors and downloads: with CRLF line endings, with a trailing
+/// newline, and with a byte order mark and trailing whitespace; all of them normalise to the same input.
+pub fn variants(input: &str) -> Vec<String> {
+    return vec![
+        input.replace('\n', "\r\n"),
+        format!("{}\n", input),
+        format!("\u{feff}{}  \r\n\r\n", input.replace('\n', " \r\n")),
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("  a\nb"), Cow::Borrowed("  a\nb")));
+        assert_eq!(normalize("\u{feff}a \r\n\r\n  b\t\r\n\n"), "a\n\n  b");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn test_variants() {
+        let input: &str = "    [D]\n[N] [C]\n\n1 2";
+        for variant in variants(input) {
+            assert_eq!(normalize(&variant), input);
+        }
+    }
+}
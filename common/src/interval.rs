@@ -0,0 +1,284 @@
+//! Integer intervals, sets of intervals and piecewise offset mappings of intervals.
+//!
+//! Intervals are half-open, `[start, end)`; closed intervals are converted with [`Interval::closed`].
+
+use std::cmp::{max, min};
+use std::fmt::{Debug, Formatter};
+use std::ops::{Add, Sub};
+
+/// Integer type usable as an interval bound.
+pub trait Bound: Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! bound {
+    ($($t:ty),*) => {
+        $(impl Bound for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })*
+    };
+}
+
+bound!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Debug> Debug for Interval<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "[{:?}, {:?})", self.start, self.end);
+    }
+}
+
+impl<T: Bound> Interval<T> {
+    /// Interval from `start` to `end`, excluding `end`.
+    pub fn create(start: T, end: T) -> Interval<T> {
+        return Interval { start, end };
+    }
+
+    /// Interval from `first` to `last`, both included.
+    pub fn closed(first: T, last: T) -> Interval<T> {
+        return Interval { start: first, end: last + T::ONE };
+    }
+
+    /// Last value of a non-empty interval.
+    pub fn last(&self) -> T {
+        return self.end - T::ONE;
+    }
+
+    pub fn len(&self) -> T {
+        return if self.is_empty() { T::ZERO } else { self.end - self.start };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.start >= self.end;
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        return self.start <= value && value < self.end;
+    }
+
+    /// Whether every value of the other interval is in this one.
+    pub fn covers(&self, other: &Interval<T>) -> bool {
+        return other.is_empty() || (self.start <= other.start && other.end <= self.end);
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        return !self.intersection(other).is_empty();
+    }
+
+    /// Common part of both intervals, possibly empty.
+    pub fn intersection(&self, other: &Interval<T>) -> Interval<T> {
+        return Interval { start: max(self.start, other.start), end: min(self.end, other.end) };
+    }
+
+    /// Values lower than the point and the rest, either of them may be empty.
+    pub fn split_at(&self, point: T) -> (Interval<T>, Interval<T>) {
+        let point: T = point.clamp(self.start, max(self.start, self.end));
+        return (Interval { start: self.start, end: point }, Interval { start: point, end: self.end });
+    }
+}
+
+/// Set of values stored as sorted, disjoint and non-adjacent intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Bound> Default for IntervalSet<T> {
+    fn default() -> Self {
+        return IntervalSet { intervals: Vec::new() };
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals: Vec<Interval<T>> = iter.into_iter().filter(|it| !it.is_empty()).collect();
+        intervals.sort();
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = max(last.end, interval.end),
+                _ => merged.push(interval),
+            }
+        }
+
+        return IntervalSet { intervals: merged };
+    }
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        return IntervalSet::default();
+    }
+
+    pub fn intervals(&self) -> impl Iterator<Item = &Interval<T>> + '_ {
+        return self.intervals.iter();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.intervals.is_empty();
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> T {
+        return self.intervals.iter().fold(T::ZERO, |acc, it| acc + it.len());
+    }
+
+    pub fn min(&self) -> Option<T> {
+        return self.intervals.first().map(|it| it.start);
+    }
+
+    pub fn max(&self) -> Option<T> {
+        return self.intervals.last().map(|it| it.last());
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx: usize = self.intervals.partition_point(|it| it.end <= value);
+        return self.intervals.get(idx).is_some_and(|it| it.contains(value));
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        *self = self.intervals.iter().copied().chain([interval]).collect();
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        return self.intervals.iter().chain(other.intervals.iter()).copied().collect();
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result: Vec<Interval<T>> = Vec::new();
+        let (mut left, mut right) = (0, 0);
+
+        while left < self.intervals.len() && right < other.intervals.len() {
+            let (a, b) = (self.intervals[left], other.intervals[right]);
+            let common: Interval<T> = a.intersection(&b);
+            if !common.is_empty() {
+                result.push(common);
+            }
+            if a.end < b.end { left += 1 } else { right += 1 }
+        }
+
+        return IntervalSet { intervals: result };
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result: Vec<Interval<T>> = Vec::new();
+
+        for interval in &self.intervals {
+            let mut rest: Interval<T> = *interval;
+            for removed in other.intervals.iter().filter(|it| it.overlaps(interval)) {
+                let (before, _) = rest.split_at(removed.start);
+                let (_, after) = rest.split_at(removed.end);
+                if !before.is_empty() {
+                    result.push(before);
+                }
+                rest = after;
+            }
+            if !rest.is_empty() {
+                result.push(rest);
+            }
+        }
+
+        return IntervalSet { intervals: result };
+    }
+
+    /// Values lower than the point and the rest.
+    pub fn split_at(&self, point: T) -> (IntervalSet<T>, IntervalSet<T>) {
+        let (below, above): (Vec<_>, Vec<_>) = self.intervals.iter().map(|it| it.split_at(point)).unzip();
+        return (below.into_iter().collect(), above.into_iter().collect());
+    }
+}
+
+/// Mapping shifting every interval of its source ranges by its own offset, other values are kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OffsetMap<T> {
+    /// Source ranges with the start of their destinations, sorted by the source.
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T: Bound> OffsetMap<T> {
+    pub fn new() -> OffsetMap<T> {
+        return OffsetMap { pieces: Vec::new() };
+    }
+
+    /// Maps the source interval to the interval of the same length starting at `destination`.
+    ///
+    /// The source interval must not overlap the sources inserted before.
+    pub fn insert(&mut self, source: Interval<T>, destination: T) {
+        let idx: usize = self.pieces.partition_point(|(it, _)| it.start < source.start);
+        self.pieces.insert(idx, (source, destination));
+    }
+
+    pub fn map(&self, value: T) -> T {
+        return match self.pieces.iter().find(|(source, _)| source.contains(value)) {
+            Some((source, destination)) => value - source.start + *destination,
+            None => value,
+        };
+    }
+
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut mapped: Vec<Interval<T>> = Vec::new();
+
+        for (source, destination) in &self.pieces {
+            for interval in set.intervals() {
+                let common: Interval<T> = source.intersection(interval);
+                if !common.is_empty() {
+                    let start: T = common.start - source.start + *destination;
+                    mapped.push(Interval { start, end: start + common.len() });
+                }
+            }
+        }
+
+        let sources: IntervalSet<T> = self.pieces.iter().map(|(source, _)| *source).collect();
+        mapped.extend(set.difference(&sources).intervals());
+
+        return mapped.into_iter().collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let interval: Interval<u32> = Interval::closed(2, 4);
+        assert_eq!(interval, Interval::create(2, 5));
+        assert_eq!((interval.len(), interval.last()), (3, 4));
+        assert!(interval.covers(&Interval::closed(3, 4)) && !interval.covers(&Interval::closed(4, 5)));
+        assert!(interval.overlaps(&Interval::closed(4, 8)) && !interval.overlaps(&Interval::closed(5, 8)));
+        assert_eq!(interval.split_at(3), (Interval::create(2, 3), Interval::create(3, 5)));
+        assert_eq!(interval.split_at(0), (Interval::create(2, 2), interval));
+        assert_eq!(Interval::create(5, 2).len(), 0);
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let set: IntervalSet<i32> = [Interval::create(5, 8), Interval::create(0, 2), Interval::create(2, 3)].into_iter().collect();
+        assert_eq!(set.intervals().copied().collect::<Vec<_>>(), vec![Interval::create(0, 3), Interval::create(5, 8)]);
+        assert_eq!((set.len(), set.min(), set.max()), (6, Some(0), Some(7)));
+        assert!(set.contains(6) && !set.contains(4) && !set.contains(8));
+
+        let other: IntervalSet<i32> = [Interval::create(1, 6)].into_iter().collect();
+        let intervals = |set: IntervalSet<i32>| set.intervals().map(|it| (it.start, it.end)).collect::<Vec<_>>();
+        assert_eq!(intervals(set.union(&other)), vec![(0, 8)]);
+        assert_eq!(intervals(set.intersection(&other)), vec![(1, 3), (5, 6)]);
+        assert_eq!(intervals(set.difference(&other)), vec![(0, 1), (6, 8)]);
+        assert_eq!(intervals(set.split_at(6).0), vec![(0, 3), (5, 6)]);
+
+        let mut map: OffsetMap<u64> = OffsetMap::new();
+        map.insert(Interval::create(98, 100), 50);
+        map.insert(Interval::create(50, 98), 52);
+        assert_eq!((map.map(79), map.map(99), map.map(10)), (81, 51, 10));
+        let seeds: IntervalSet<u64> = [Interval::create(40, 60), Interval::create(97, 101)].into_iter().collect();
+        let mapped: Vec<(u64, u64)> = map.map_set(&seeds).intervals().map(|it| (it.start, it.end)).collect();
+        assert_eq!(mapped, vec![(40, 62), (99, 101)]);
+    }
+}
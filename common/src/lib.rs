@@ -1 +1,20 @@
+pub mod answer;
+pub mod cli;
+pub mod cycle;
+pub mod error;
+pub mod geom;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod linalg;
+pub mod search;
 pub mod math;
+pub mod memo;
+pub mod par;
+pub mod parse;
+pub mod poly;
+pub mod report;
+pub mod solution;
+
+pub use answer::Answer;
+pub use solution::Solution;
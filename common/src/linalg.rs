@@ -0,0 +1,145 @@
+//! Exact linear algebra: Gaussian elimination over big rationals and intersections of lines.
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::Zero;
+
+use crate::geom::Point3;
+
+/// Brings the augmented matrix to reduced row echelon form, returns the pivot column of every non-zero row.
+fn eliminate(rows: &mut [Vec<BigRational>], columns: usize) -> Vec<usize> {
+    let mut pivots: Vec<usize> = Vec::new();
+
+    for column in 0..columns {
+        let row: usize = pivots.len();
+        let Some(pivot) = (row..rows.len()).find(|it| !rows[*it][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(row, pivot);
+
+        let factor: BigRational = rows[row][column].clone();
+        rows[row] = rows[row].iter().map(|it| it.clone() / factor.clone()).collect();
+
+        for other in (0..rows.len()).filter(|it| *it != row) {
+            let factor: BigRational = rows[other][column].clone();
+            if factor.is_zero() {
+                continue;
+            }
+            for idx in column..rows[other].len() {
+                let value: BigRational = rows[row][idx].clone() * factor.clone();
+                rows[other][idx] = rows[other][idx].clone() - value;
+            }
+        }
+
+        pivots.push(column);
+    }
+
+    return pivots;
+}
+
+/// Unique solution `x` of `matrix * x = rhs`, `None` when the system has no solution or infinitely many.
+pub fn solve(matrix: &[Vec<BigRational>], rhs: &[BigRational]) -> Option<Vec<BigRational>> {
+    let columns: usize = matrix.first().map_or(0, |it| it.len());
+    let mut rows: Vec<Vec<BigRational>> = matrix.iter().zip(rhs)
+        .map(|(row, value)| row.iter().cloned().chain([value.clone()]).collect())
+        .collect();
+
+    let pivots: Vec<usize> = eliminate(&mut rows, columns);
+    let inconsistent: bool = rows[pivots.len()..].iter().any(|it| !it[columns].is_zero());
+    if pivots.len() < columns || inconsistent {
+        return None;
+    }
+
+    return Some(rows.into_iter().take(columns).map(|it| it[columns].clone()).collect());
+}
+
+/// Number of linearly independent rows of the matrix.
+pub fn rank(matrix: &[Vec<BigRational>]) -> usize {
+    let columns: usize = matrix.first().map_or(0, |it| it.len());
+    return eliminate(&mut matrix.to_vec(), columns).len();
+}
+
+/// Integer as a rational.
+pub fn rational(value: i64) -> BigRational {
+    return BigRational::from_integer(BigInt::from(value));
+}
+
+/// Parameters `(t, s)` of the single intersection `p1 + t * d1 = p2 + s * d2` of two lines in the plane.
+///
+/// `None` when the lines are parallel, including identical lines.
+pub fn intersect_lines_2d(p1: (i64, i64), d1: (i64, i64), p2: (i64, i64), d2: (i64, i64)) -> Option<(BigRational, BigRational)> {
+    let cross = |a: (BigInt, BigInt), b: (BigInt, BigInt)| a.0 * b.1 - a.1 * b.0;
+    let pair = |it: (i64, i64)| (BigInt::from(it.0), BigInt::from(it.1));
+    let delta: (BigInt, BigInt) = (BigInt::from(p2.0) - p1.0, BigInt::from(p2.1) - p1.1);
+
+    let denominator: BigInt = cross(pair(d1), pair(d2));
+    if denominator.is_zero() {
+        return None;
+    }
+
+    return Some((
+        BigRational::new(cross(delta.clone(), pair(d2)), denominator.clone()),
+        BigRational::new(cross(delta, pair(d1)), denominator),
+    ));
+}
+
+/// Parameters `(t, s)` of the single intersection `p1 + t * d1 = p2 + s * d2` of two lines in space.
+///
+/// `None` when the lines are parallel or skew.
+pub fn intersect_lines_3d(p1: Point3, d1: Point3, p2: Point3, d2: Point3) -> Option<(BigRational, BigRational)> {
+    let axes = |it: Point3| [it.x, it.y, it.z];
+    let (p1, d1, p2, d2) = (axes(p1), axes(d1), axes(p2), axes(d2));
+
+    // any pair of axes where the projections are not parallel determines the parameters
+    let (t, s) = [(0, 1), (0, 2), (1, 2)].iter().find_map(|(a, b)| {
+        return intersect_lines_2d((p1[*a], p1[*b]), (d1[*a], d1[*b]), (p2[*a], p2[*b]), (d2[*a], d2[*b]));
+    })?;
+
+    let matches: bool = (0..3).all(|axis| {
+        return rational(p1[axis]) + &t * rational(d1[axis]) == rational(p2[axis]) + &s * rational(d2[axis]);
+    });
+    return if matches { Some((t, s)) } else { None };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rationals(values: &[i64]) -> Vec<BigRational> {
+        return values.iter().map(|it| rational(*it)).collect();
+    }
+
+    fn fraction(num: i64, den: i64) -> BigRational {
+        return rational(num) / rational(den);
+    }
+
+    #[test]
+    fn test_solve() {
+        let matrix: Vec<Vec<BigRational>> = vec![rationals(&[2, 1, -1]), rationals(&[-3, -1, 2]), rationals(&[-2, 1, 2])];
+        assert_eq!(solve(&matrix, &rationals(&[8, -11, -3])), Some(rationals(&[2, 3, -1])));
+        assert_eq!(rank(&matrix), 3);
+
+        let singular: Vec<Vec<BigRational>> = vec![rationals(&[1, 2]), rationals(&[2, 4])];
+        assert_eq!(solve(&singular, &rationals(&[3, 6])), None);
+        assert_eq!(solve(&singular, &rationals(&[3, 7])), None);
+        assert_eq!(rank(&singular), 1);
+
+        let big: Vec<Vec<BigRational>> = vec![rationals(&[i64::MAX, 1]), rationals(&[1, i64::MIN])];
+        let rhs: Vec<BigRational> = vec![rational(i64::MAX - 1), rational(1) - rational(i64::MIN)];
+        assert_eq!(solve(&big, &rhs), Some(rationals(&[1, -1])));
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(intersect_lines_2d((19, 13), (-2, 1), (18, 19), (-1, -1)), Some((fraction(7, 3), fraction(11, 3))));
+        assert_eq!(intersect_lines_2d((0, 0), (0, 1), (2, 5), (-1, 0)), Some((rational(5), rational(2))));
+        assert_eq!(intersect_lines_2d((0, 0), (1, 1), (1, 0), (2, 2)), None);
+
+        let origin: Point3 = Point3::create(0, 0, 0);
+        assert_eq!(
+            intersect_lines_3d(origin, Point3::create(1, 1, 1), Point3::create(2, 0, 2), Point3::create(0, 1, 0)),
+            Some((rational(2), rational(2)))
+        );
+        assert_eq!(intersect_lines_3d(origin, Point3::create(1, 0, 0), Point3::create(0, 1, 1), Point3::create(0, 0, 1)), None);
+    }
+}
@@ -1,58 +1,220 @@
-pub fn gcd(n: u64, m: u64) -> Option<u64> {
-    if n == 0 || m == 0 {
-        return None;
-    }
+//! Number theory over primitive integers.
+//!
+//! `gcd` and `lcm` are generic over all primitive integers; modular arithmetic works on `i64` values and computes
+//! on `i128`, so intermediate products never overflow.
 
-    let mut m1: u64 = m;
-    let mut n1: u64 = n;
+use std::fmt::Debug;
+use std::ops::{Div, Rem};
 
-    while m1 != 0 {
-        if m1 < n1 {
-            std::mem::swap(&mut m1, &mut n1);
-        }
-        m1 %= n1;
+/// Primitive integer type.
+pub trait Integer: Copy + Ord + Debug + Div<Output = Self> + Rem<Output = Self> {
+    /// Unsigned type of the same width, which holds the absolute value of every value
+    type Unsigned: Integer<Unsigned = Self::Unsigned>;
+
+    const ZERO: Self;
+
+    fn unsigned_abs(self) -> Self::Unsigned;
+
+    /// Value of the unsigned number, `None` when it does not fit.
+    fn from_unsigned(value: Self::Unsigned) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty => $u:ty),*) => {
+        $(impl Integer for $t {
+            type Unsigned = $u;
+
+            const ZERO: Self = 0;
+
+            #[allow(clippy::unnecessary_cast)]
+            fn unsigned_abs(self) -> $u {
+                return self.abs_diff(0) as $u;
+            }
+
+            fn from_unsigned(value: $u) -> Option<Self> {
+                return <$t>::try_from(value).ok();
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                return <$t>::checked_mul(self, other);
+            }
+        })*
+    };
+}
+
+integer!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+integer!(u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize);
+
+/// Greatest common divisor as an unsigned number, so that it fits even for `i64::MIN`; `gcd(n, 0)` is `|n|`.
+pub fn gcd<T: Integer>(n: T, m: T) -> T::Unsigned {
+    let (mut n, mut m) = (n.unsigned_abs(), m.unsigned_abs());
+    while m != <T::Unsigned as Integer>::ZERO {
+        (n, m) = (m, n % m);
     }
+    return n;
+}
 
-    return Some(n1);
+pub fn gcd_vec<T: Integer>(n: &[T]) -> T::Unsigned {
+    return n.iter().fold(<T::Unsigned as Integer>::ZERO, |acc, it| gcd(acc, it.unsigned_abs()));
 }
 
-pub fn gcd_vec(n: &Vec<u64>) -> Option<u64> {
-    if n.iter().any(|&it| it == 0) {
-        return None;
+/// Least common multiple, never negative; `None` when it overflows.
+pub fn lcm<T: Integer>(n: T, m: T) -> Option<T> {
+    if n == T::ZERO || m == T::ZERO {
+        return Some(T::ZERO);
     }
+    return T::from_unsigned((n.unsigned_abs() / gcd(n, m)).checked_mul(m.unsigned_abs())?);
+}
+
+pub fn lcm_vec<T: Integer>(n: &[T]) -> Option<T> {
+    return n.iter().try_fold(None, |acc: Option<T>, it| {
+        return match acc {
+            Some(acc) => lcm(acc, *it).map(Some),
+            None => T::from_unsigned(it.unsigned_abs()).map(Some),
+        };
+    })?;
+}
 
-    if n.len() == 1 {
-        return Some(n.first().unwrap().to_owned());
+/// Extended Euclid: `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a as i128, b as i128);
+    let (mut x0, mut x1) = (1i128, 0i128);
+    let (mut y0, mut y1) = (0i128, 1i128);
+
+    while r1 != 0 {
+        let q: i128 = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
     }
 
-    let acc = gcd(*n.get(0).unwrap(), *n.get(1).unwrap());
-    if n.len() == 2 {
-        return acc;
+    if r0 < 0 {
+        return (-r0 as i64, -x0 as i64, -y0 as i64);
     }
+    return (r0 as i64, x0 as i64, y0 as i64);
+}
 
-    return Some(n.iter().fold(acc.unwrap().to_owned(), | acc, it | gcd(acc, *it).unwrap()))
+/// Inverse of `a` modulo `m` in `0..m`, `None` when they are not coprime or the modulus is not positive.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    if g != 1 {
+        return None;
+    }
+    return Some(x.rem_euclid(m));
 }
 
-pub fn lcm(n: u64, m: u64) -> Option<u64> {
-    if n == 0 || m == 0 {
+/// `base ^ exp` modulo `m`, in `0..m`; `None` when the modulus is not positive.
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> Option<i64> {
+    if m <= 0 {
         return None;
     }
-    return Some(n * m / gcd(n, m).unwrap())
+    let m: i128 = m as i128;
+    let mut base: i128 = (base as i128).rem_euclid(m);
+    let mut result: i128 = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    return Some(result as i64);
 }
 
-pub fn lcm_vec(n: &Vec<u64>) -> Option<u64> {
-    if n.iter().any(|&it| it == 0) {
+/// Chinese Remainder Theorem: `(r, m)` such that `x ≡ r (mod m)` exactly when `x ≡ r_i (mod m_i)` for every
+/// `(r_i, m_i)`, with `m` the least common multiple of the moduli.
+///
+/// The moduli don't have to be coprime. `None` when there are no congruences, a modulus is not positive, the
+/// congruences contradict each other or `m` overflows.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    if congruences.is_empty() {
         return None;
     }
+    let mut result: (i128, i128) = (0, 1);
+
+    for (remainder, modulus) in congruences {
+        if *modulus <= 0 {
+            return None;
+        }
+        let (r1, m1) = result;
+        let (r2, m2) = ((*remainder as i128).rem_euclid(*modulus as i128), *modulus as i128);
+
+        let (g, x, _) = ext_gcd(m1 as i64, m2 as i64);
+        let g: i128 = g as i128;
+        if (r2 - r1) % g != 0 {
+            return None;
+        }
+
+        let modulus: i128 = m1 / g * m2;
+        if modulus > i64::MAX as i128 {
+            return None;
+        }
+        // r1 + m1 * k with k ≡ (r2 - r1) / g * x (mod m2 / g)
+        let k: i128 = ((r2 - r1) / g % (m2 / g) * (x as i128 % (m2 / g))).rem_euclid(m2 / g);
+        result = ((r1 + m1 * k).rem_euclid(modulus), modulus);
+    }
+
+    return Some((result.0 as i64, result.1 as i64));
+}
 
-    if n.len() == 1 {
-        return Some(n.first().unwrap().to_owned());
+/// First time `t >= offset_i` for every cycle, at which every cycle is at its `offset_i + k * period_i`; `None`
+/// without cycles.
+pub fn first_common(cycles: &[(i64, i64)]) -> Option<i64> {
+    let (remainder, modulus) = crt(cycles)?;
+    let earliest: i64 = cycles.iter().map(|it| it.0).max().unwrap_or(0);
+    if earliest <= remainder {
+        return Some(remainder);
     }
+    let cycles_count: i64 = (earliest - remainder + modulus - 1) / modulus;
+    return cycles_count.checked_mul(modulus)?.checked_add(remainder);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let acc = lcm(*n.get(0).unwrap(), *n.get(1).unwrap());
-    if n.len() == 2 {
-        return acc;
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(-12i32, 18), 6);
+        assert_eq!(gcd(0u8, 7), 7);
+        assert_eq!(gcd_vec(&[12usize, 18, 27]), 3);
+        assert_eq!(lcm(4i64, -6), Some(12));
+        assert_eq!(lcm(0u32, 5), Some(0));
+        assert_eq!(lcm(200u8, 3), None);
+        assert_eq!(lcm_vec(&[2u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_vec::<u64>(&[]), None);
+        assert_eq!(gcd(i64::MIN, 0), 1 << 63);
+        assert_eq!(lcm(i64::MIN, 2), None);
+        assert_eq!(lcm_vec(&[i32::MIN]), None);
     }
 
-    return Some(n.iter().fold(acc.unwrap().to_owned(), | acc, it | lcm(acc, *it).unwrap()))
-}
\ No newline at end of file
+    #[test]
+    fn test_modular() {
+        let (g, x, y) = ext_gcd(240, 46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_pow(4, 13, 497), Some(445));
+        assert_eq!(mod_pow(-2, 3, 5), Some(2));
+        assert_eq!(mod_pow(2, 3, 0), None);
+
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(1, 4), (0, 0)]), None);
+        assert_eq!(first_common(&[(0, 0)]), None);
+        assert_eq!(crt(&[]), None);
+        assert_eq!(first_common(&[]), None);
+        assert_eq!(first_common(&[(2, 2), (3, 3)]), Some(6));
+        assert_eq!(first_common(&[(3, 4), (1, 6)]), Some(7));
+    }
+}
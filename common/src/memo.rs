@@ -0,0 +1,163 @@
+//! Memoisation of recursive functions.
+//!
+//! The function keeps the recursion itself, `Memo::get_or_compute` hands the memo back to the computation so the
+//! recursive calls go through the same cache:
+//!
+//! ```
+//! use common::memo::Memo;
+//!
+//! fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+//!     return memo.get_or_compute(n, |memo, n| if n < 2 { n } else { fib(memo, n - 1) + fib(memo, n - 2) });
+//! }
+//!
+//! assert_eq!(fib(&mut Memo::new(), 90), 2880067194370816120);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::iter::Sum;
+#[cfg(debug_assertions)]
+use std::sync::Mutex;
+
+/// Number of lookups answered from the cache and computed, counted in debug builds only.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let total: usize = self.hits + self.misses;
+        let ratio: f64 = if total == 0 { 0.0 } else { self.hits as f64 * 100.0 / total as f64 };
+        return write!(f, "{} hits, {} misses ({:.1}% hit rate)", self.hits, self.misses, ratio);
+    }
+}
+
+/// Total of the lookups of several memos, e.g. one per line of the input.
+impl Sum for Stats {
+    fn sum<I: Iterator<Item = Stats>>(iter: I) -> Self {
+        return iter.fold(Stats::default(), |acc, it| Stats { hits: acc.hits + it.hits, misses: acc.misses + it.misses });
+    }
+}
+
+/// Lookups of every memo dropped so far.
+#[cfg(debug_assertions)]
+static TOTAL: Mutex<Stats> = Mutex::new(Stats { hits: 0, misses: 0 });
+
+/// Hits and misses of all memos dropped so far, `None` in release builds which do not count them.
+pub fn total_stats() -> Option<Stats> {
+    #[cfg(debug_assertions)]
+    return TOTAL.lock().ok().map(|it| *it);
+    #[cfg(not(debug_assertions))]
+    return None;
+}
+
+/// Cache of the values of a function by its arguments.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    #[cfg(debug_assertions)]
+    stats: Stats,
+}
+
+impl<K: Eq + Hash + Copy, V: Clone> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        return Memo {
+            cache: HashMap::new(),
+            #[cfg(debug_assertions)]
+            stats: Stats::default(),
+        };
+    }
+
+    /// Value of the key, computed on the first lookup only; `compute` gets the memo for its recursive lookups.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Memo<K, V>, K) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            #[cfg(debug_assertions)]
+            {
+                self.stats.hits += 1;
+            }
+            return value.clone();
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            self.stats.misses += 1;
+        }
+        let value: V = compute(self, key);
+        self.cache.insert(key, value.clone());
+        return value;
+    }
+
+    /// Number of cached values.
+    pub fn len(&self) -> usize {
+        return self.cache.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.cache.is_empty();
+    }
+
+    /// Hits and misses of the lookups so far, `None` in release builds which do not count them.
+    pub fn stats(&self) -> Option<Stats> {
+        #[cfg(debug_assertions)]
+        return Some(self.stats);
+        #[cfg(not(debug_assertions))]
+        return None;
+    }
+}
+
+#[cfg(debug_assertions)]
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        if let Ok(mut total) = TOTAL.lock() {
+            *total = [*total, self.stats].into_iter().sum();
+        }
+    }
+}
+
+impl<K: Eq + Hash + Copy, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        return Memo::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(memo: &mut Memo<(u32, u32), u64>, row: u32, col: u32) -> u64 {
+        return memo.get_or_compute((row, col), |memo, (row, col)| {
+            if row == 0 || col == 0 {
+                return 1;
+            }
+            return paths(memo, row - 1, col) + paths(memo, row, col - 1);
+        });
+    }
+
+    #[test]
+    fn test_get_or_compute() {
+        let mut memo: Memo<(u32, u32), u64> = Memo::new();
+        assert_eq!(paths(&mut memo, 16, 16), 601080390);
+        assert_eq!(memo.len(), 17 * 17 - 1);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_stats() {
+        let mut memo: Memo<(u32, u32), u64> = Memo::new();
+        paths(&mut memo, 2, 2);
+        assert_eq!(memo.stats(), Some(Stats { hits: 1, misses: 8 }));
+        assert_eq!(memo.stats().unwrap().to_string(), "1 hits, 8 misses (11.1% hit rate)");
+
+        let total: Option<Stats> = [memo.stats(), Some(Stats { hits: 3, misses: 2 })].into_iter().sum();
+        assert_eq!(total, Some(Stats { hits: 4, misses: 10 }));
+
+        // other tests may drop their memos concurrently, so the total only grows by at least this memo
+        let before: Stats = total_stats().unwrap();
+        drop(memo);
+        let after: Stats = total_stats().unwrap();
+        assert!(after.hits > before.hits && after.misses >= before.misses + 8);
+    }
+}
@@ -0,0 +1,37 @@
+//! Loops over independent items, run on all cores when the `parallel` feature is enabled.
+//!
+//! Results are returned in the order of the items, so folding them gives the same answer with and without the
+//! feature.
+
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Maps every item of the slice.
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "parallel")]
+    return items.par_iter().map(f).collect();
+    #[cfg(not(feature = "parallel"))]
+    return items.iter().map(f).collect();
+}
+
+/// Maps every index of the range.
+pub fn map_range<R: Send>(range: Range<usize>, f: impl Fn(usize) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "parallel")]
+    return range.into_par_iter().map(f).collect();
+    #[cfg(not(feature = "parallel"))]
+    return range.map(f).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        assert_eq!(map(&[3, 1, 2], |it| it * 2), vec![6, 2, 4]);
+        assert_eq!(map_range(2..5, |it| it * it), vec![4, 9, 16]);
+        assert_eq!(map_range(0..0, |it| it), Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,233 @@
+//! Helpers for parsing puzzle inputs with errors pointing at the line and column which failed.
+//!
+//! A [`Parser`] wraps the whole input; the parts passed to its helpers must be slices of that input (e.g. from
+//! `split`, `lines` or `trim`), so that an error can locate them. Parsers of nested parts implementing `FromStr`
+//! with [`ParseError`] are located within the outer input by [`Parser::parse`].
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::grid::{Grid, GridError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the error, starting at 1
+    pub line: usize,
+    /// Column of the error in characters, starting at 1
+    pub column: usize,
+    /// Whole line of the error
+    pub snippet: String,
+    pub message: String,
+    offset: usize,
+}
+
+impl ParseError {
+    /// Error about the part of the input, which must be a slice of it; other parts are reported at its start.
+    pub fn at(input: &str, part: &str, message: impl Display) -> ParseError {
+        return ParseError::at_offset(input, offset_of(input, part), message.to_string());
+    }
+
+    fn at_offset(input: &str, offset: usize, message: String) -> ParseError {
+        let offset: usize = offset.min(input.len());
+        let line_start: usize = input[..offset].rfind('\n').map_or(0, |it| it + 1);
+        let line_end: usize = input[offset..].find('\n').map_or(input.len(), |it| offset + it);
+
+        return ParseError {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end].trim_end_matches('\r').to_string(),
+            message,
+            offset,
+        };
+    }
+
+    /// Locates the error of a nested parser of the part within the whole input.
+    pub fn rebase(self, input: &str, part: &str) -> ParseError {
+        return ParseError::at_offset(input, offset_of(input, part) + self.offset, self.message);
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let caret: String = " ".repeat(self.column - 1);
+        return write!(f, "line {}, column {}: {}\n    {}\n    {}^", self.line, self.column, self.message, self.snippet, caret);
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Byte offset of the part in the input, 0 when it is not a slice of the input.
+fn offset_of(input: &str, part: &str) -> usize {
+    let start: usize = input.as_ptr() as usize;
+    let position: usize = part.as_ptr() as usize;
+    if position < start || position > start + input.len() {
+        return 0;
+    }
+    return position - start;
+}
+
+/// Parsing helpers over a whole puzzle input.
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    pub fn create(input: &'a str) -> Parser<'a> {
+        return Parser { input };
+    }
+
+    pub fn input(&self) -> &'a str {
+        return self.input;
+    }
+
+    pub fn error(&self, part: &str, message: impl Display) -> ParseError {
+        return ParseError::at(self.input, part, message);
+    }
+
+    /// Lines of the whole input.
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        return self.input.lines();
+    }
+
+    /// Parts of the input separated by blank lines.
+    pub fn blocks(&self, part: &'a str) -> Vec<&'a str> {
+        let mut blocks: Vec<&'a str> = Vec::new();
+        let mut start: Option<usize> = None;
+        let mut end: usize = 0;
+        let mut offset: usize = 0;
+
+        for line in part.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                if let Some(it) = start.take() {
+                    blocks.push(&part[it..end]);
+                }
+            } else {
+                start.get_or_insert(offset);
+                end = offset + line.trim_end().len();
+            }
+            offset += line.len();
+        }
+        if let Some(it) = start {
+            blocks.push(&part[it..end]);
+        }
+
+        return blocks;
+    }
+
+    /// Parses the trimmed part with its `FromStr`, reporting errors of other types at the start of the part.
+    pub fn value<T: FromStr>(&self, part: &'a str) -> Result<T, ParseError> where T::Err: Display {
+        let part: &str = part.trim();
+        return part.parse::<T>().map_err(|err| self.error(part, format!("invalid value '{}': {}", part, err)));
+    }
+
+    /// Parses the part with its `FromStr`, locating the errors of the nested parser within the whole input.
+    pub fn parse<T: FromStr<Err = ParseError>>(&self, part: &'a str) -> Result<T, ParseError> {
+        return part.parse::<T>().map_err(|err| err.rebase(self.input, part));
+    }
+
+    /// Integers separated by whitespace or commas.
+    pub fn numbers<T: FromStr>(&self, part: &'a str) -> Result<Vec<T>, ParseError> where T::Err: Display {
+        return part.split(|it: char| it.is_whitespace() || it == ',')
+            .filter(|it| !it.is_empty())
+            .map(|it| self.value(it))
+            .collect();
+    }
+
+    /// Exactly `N` fields of the part separated by the separator.
+    pub fn fields<const N: usize>(&self, part: &'a str, separator: &str) -> Result<[&'a str; N], ParseError> {
+        let fields: Vec<&'a str> = part.split(separator).collect();
+        return fields.try_into().map_err(|fields: Vec<&str>| {
+            return self.error(part, format!("expected {} fields separated by '{}', found {}", N, separator, fields.len()));
+        });
+    }
+
+    /// Trimmed key and value of a `key: value` like line.
+    pub fn key_value(&self, line: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+        return match line.split_once(separator) {
+            Some((key, value)) => Ok((key.trim(), value.trim())),
+            None => Err(self.error(line, format!("expected '{}'", separator))),
+        };
+    }
+
+    pub fn strip_prefix(&self, part: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        return part.strip_prefix(prefix).ok_or_else(|| self.error(part, format!("expected '{}'", prefix)));
+    }
+
+    pub fn strip_suffix(&self, part: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+        return part.strip_suffix(suffix).ok_or_else(|| {
+            let end: &str = &part[part.len().saturating_sub(suffix.len())..];
+            return self.error(end, format!("expected '{}'", suffix));
+        });
+    }
+
+    /// Grid of the part with a cell per character.
+    pub fn grid<T>(&self, part: &'a str, cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, ParseError> {
+        return Grid::parse_with(part, cell).map_err(|err| {
+            // rows and columns of grid errors start at 1
+            let line = |row: usize| part.lines().nth(row - 1).unwrap_or(part);
+            return match err {
+                GridError::Empty => self.error(part, &err),
+                GridError::Ragged { row, .. } => self.error(line(row), &err),
+                GridError::Cell { row, col, .. } => {
+                    let line: &str = line(row);
+                    let start: usize = line.char_indices().nth(col - 1).map_or(0, |(idx, _)| idx);
+                    self.error(&line[start..], &err)
+                }
+            };
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Point(i32, i32);
+
+    impl FromStr for Point {
+        type Err = ParseError;
+
+        fn from_str(input: &str) -> Result<Self, Self::Err> {
+            let parser: Parser = Parser::create(input);
+            let [x, y] = parser.fields(input, ",")?;
+            return Ok(Point(parser.value(x)?, parser.value(y)?));
+        }
+    }
+
+    #[test]
+    fn test_helpers() {
+        let input: &str = "seeds: 79 14, 55\n\nname: value\n  \nx=1\ny=2\n";
+        let parser: Parser = Parser::create(input);
+
+        let blocks: Vec<&str> = parser.blocks(input);
+        assert_eq!(blocks, vec!["seeds: 79 14, 55", "name: value", "x=1\ny=2"]);
+
+        let (key, values) = parser.key_value(blocks[0], ":").unwrap();
+        assert_eq!(key, "seeds");
+        assert_eq!(parser.numbers::<u32>(values).unwrap(), vec![79, 14, 55]);
+        assert_eq!(parser.strip_prefix(blocks[1], "name:").unwrap(), " value");
+        assert_eq!(parser.fields::<2>(blocks[2], "\n").unwrap(), ["x=1", "y=2"]);
+        assert_eq!(parser.grid(".#\n#.", |it| Some(it == '#')).unwrap().height(), 2);
+    }
+
+    #[test]
+    fn test_errors() {
+        let input: &str = "1,2\n3,x4\n";
+        let parser: Parser = Parser::create(input);
+
+        assert_eq!(parser.parse::<Point>(&input[..3]), Ok(Point(1, 2)));
+        let err: ParseError = input.lines().map(|it| parser.parse::<Point>(it)).collect::<Result<Vec<Point>, _>>().unwrap_err();
+        assert_eq!((err.line, err.column, err.snippet.as_str()), (2, 3, "3,x4"));
+        assert_eq!(err.to_string(), "line 2, column 3: invalid value 'x4': invalid digit found in string\n    3,x4\n      ^");
+
+        let err: ParseError = parser.grid(&input[..6], Some).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err: ParseError = parser.grid(&input[4..7], |it| it.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+
+        assert_eq!(parser.key_value(&input[..3], ":").unwrap_err().message, "expected ':'");
+        assert_eq!(parser.strip_suffix(&input[..3], ")").unwrap_err().column, 3);
+    }
+}
@@ -0,0 +1,131 @@
+//! Polynomial extrapolation of sequences.
+//!
+//! A sequence generated by a polynomial of degree `d` is fully determined by `d + 1` of its values, so its values at
+//! far away indices can be computed without generating the terms in between.
+
+use num_rational::BigRational;
+use num_traits::{One, Zero};
+
+use crate::linalg::rational;
+
+/// First value of every row of the difference table of the values, up to the last non-zero row.
+///
+/// These are the coefficients of the Newton forward difference formula.
+pub fn differences(values: &[i64]) -> Vec<i128> {
+    let mut row: Vec<i128> = values.iter().map(|it| *it as i128).collect();
+    let mut result: Vec<i128> = Vec::new();
+
+    while row.iter().any(|it| *it != 0) {
+        result.push(row[0]);
+        row = row.windows(2).map(|it| it[1] - it[0]).collect();
+    }
+
+    return result;
+}
+
+/// Value at index `x` of the polynomial sequence whose values at indices `0, 1, 2, ...` are given.
+///
+/// The index may be negative or far beyond the given values; the sequence must have a constant difference row
+/// within the given values, otherwise the result is the value of the lowest degree polynomial through all of them.
+pub fn extrapolate(values: &[i64], x: i64) -> i128 {
+    let x: i128 = x as i128;
+    let mut binomial: i128 = 1;
+    let mut result: i128 = 0;
+
+    for (k, difference) in differences(values).into_iter().enumerate() {
+        // C(x, k) = C(x, k - 1) * (x - k + 1) / k, always divisible
+        if k > 0 {
+            binomial = binomial * (x - k as i128 + 1) / k as i128;
+        }
+        result += binomial * difference;
+    }
+
+    return result;
+}
+
+/// Polynomial with exact rational coefficients, lowest degree first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<BigRational>,
+}
+
+impl Polynomial {
+    /// Lowest degree polynomial through all the points, by Lagrange interpolation.
+    ///
+    /// The `x` coordinates must be distinct.
+    pub fn fit(points: &[(BigRational, BigRational)]) -> Polynomial {
+        let mut coefficients: Vec<BigRational> = vec![BigRational::zero(); points.len()];
+
+        for (idx, (xi, yi)) in points.iter().enumerate() {
+            // basis polynomial: product of (x - xj) / (xi - xj) over other points
+            let mut basis: Vec<BigRational> = vec![BigRational::one()];
+            let mut denominator: BigRational = BigRational::one();
+
+            for (_, (xj, _)) in points.iter().enumerate().filter(|(jdx, _)| *jdx != idx) {
+                let mut next: Vec<BigRational> = vec![BigRational::zero(); basis.len() + 1];
+                for (power, coefficient) in basis.iter().enumerate() {
+                    next[power + 1] += coefficient;
+                    next[power] -= coefficient * xj;
+                }
+                basis = next;
+                denominator *= xi - xj;
+            }
+
+            let scale: BigRational = yi / denominator;
+            for (power, coefficient) in basis.iter().enumerate() {
+                coefficients[power] += coefficient * &scale;
+            }
+        }
+
+        while coefficients.last().is_some_and(|it| it.is_zero()) {
+            coefficients.pop();
+        }
+
+        return Polynomial { coefficients };
+    }
+
+    /// Polynomial through the integer points.
+    pub fn fit_integers(points: &[(i64, i64)]) -> Polynomial {
+        let points: Vec<(BigRational, BigRational)> = points.iter().map(|(x, y)| (rational(*x), rational(*y))).collect();
+        return Polynomial::fit(&points);
+    }
+
+    pub fn coefficients(&self) -> &[BigRational] {
+        return &self.coefficients;
+    }
+
+    /// Degree of the polynomial, `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        return self.coefficients.len().checked_sub(1);
+    }
+
+    pub fn eval(&self, x: &BigRational) -> BigRational {
+        return self.coefficients.iter().rev().fold(BigRational::zero(), |acc, it| acc * x + it);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(differences(&[1, 3, 6, 10, 15, 21]), vec![1, 2, 1]);
+        assert_eq!(extrapolate(&[1, 3, 6, 10, 15, 21], 6), 28);
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -1), 5);
+        assert_eq!(extrapolate(&[0, 3, 6], 1_000_000_000_000), 3_000_000_000_000);
+        assert_eq!(extrapolate(&[0, 0, 0], 5), 0);
+    }
+
+    #[test]
+    fn test_polynomial() {
+        // x^2 / 2 + x / 2 + 1
+        let polynomial: Polynomial = Polynomial::fit_integers(&[(1, 2), (3, 7), (-2, 2)]);
+        let half: BigRational = rational(1) / rational(2);
+        assert_eq!(polynomial.coefficients(), &[rational(1), half.clone(), half.clone()]);
+        assert_eq!(polynomial.degree(), Some(2));
+        assert_eq!(polynomial.eval(&rational(4)), rational(11));
+        assert_eq!(polynomial.eval(&half), rational(11) / rational(8));
+        assert_eq!(Polynomial::fit_integers(&[(0, 5), (7, 5)]).degree(), Some(0));
+    }
+}
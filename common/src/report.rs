@@ -0,0 +1,170 @@
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::answer::Answer;
+use crate::error::SolveError;
+use crate::Solution;
+
+/// How the binaries print their results.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    /// Lines for humans
+    Text,
+    /// Single JSON document
+    Json,
+    /// One JSON object per line and solved part
+    Ndjson,
+}
+
+/// Answer of a single part with the time spent in each stage.
+pub struct Timed {
+    pub answer: Result<Answer, SolveError>,
+    pub parse: Duration,
+    /// `None` when the input could not be parsed
+    pub solve: Option<Duration>,
+}
+
+/// Solves one part of the puzzle input, or of the example of the part when there is no input, timing parse and
+/// solve separately. `None` when the puzzle has no example for the part.
+pub fn solve_timed<S: Solution>(part: u8, input: Option<&str>) -> Option<Timed> {
+    let (input, params): (&str, S::Params) = match input {
+        Some(input) => (input, S::Params::default()),
+        None => (S::EXAMPLES.get((part as usize).checked_sub(1)?)?, S::example_params()),
+    };
+
+    let start: Instant = Instant::now();
+    let parsed: Result<S::Input, SolveError> = S::parse_input(input);
+    let parse: Duration = start.elapsed();
+
+    let parsed: S::Input = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => return Some(Timed { answer: Err(err), parse, solve: None }),
+    };
+
+    let start: Instant = Instant::now();
+    let answer: Result<Answer, SolveError> = S::solve_parsed(part, &parsed, &params);
+    return Some(Timed { answer, parse, solve: Some(start.elapsed()) });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Solved,
+    Failed,
+    /// Solving the examples, but the puzzle has none for the part
+    NoExample,
+}
+
+/// Result of a single part as printed by `--format json` and `--format ndjson`.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+    pub status: Status,
+    pub error: Option<String>,
+    /// Exit code of the binaries for the record, see [`SolveError::exit_code`]
+    #[serde(skip)]
+    pub exit_code: u8,
+}
+
+impl Record {
+    pub fn create(year: u16, day: u8, part: u8, timed: Option<&Timed>) -> Record {
+        let Some(timed) = timed else {
+            return Record::failed(year, day, part, Status::NoExample, None);
+        };
+
+        let (answer, status, error, exit_code) = match &timed.answer {
+            Ok(answer) => (Some(answer.to_string()), Status::Solved, None, 0),
+            Err(err) => (None, Status::Failed, Some(err.to_string()), err.exit_code()),
+        };
+
+        return Record {
+            year,
+            day,
+            part,
+            answer,
+            parse_ns: Some(timed.parse.as_nanos() as u64),
+            solve_ns: timed.solve.map(|it| it.as_nanos() as u64),
+            status,
+            error,
+            exit_code,
+        };
+    }
+
+    /// Record of a part which was not solved at all, e.g. because its input could not be read.
+    pub fn failed(year: u16, day: u8, part: u8, status: Status, error: Option<String>) -> Record {
+        let exit_code: u8 = if status == Status::Failed { 1 } else { 0 };
+        return Record { year, day, part, answer: None, parse_ns: None, solve_ns: None, status, error, exit_code };
+    }
+
+    /// Single line of JSON, as printed by `--format ndjson`.
+    pub fn to_line(&self) -> String {
+        return serde_json::to_string(self).unwrap();
+    }
+}
+
+/// JSON document of the records, as printed by `--format json`.
+pub fn to_document(records: &[Record]) -> String {
+    return serde_json::to_string_pretty(records).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{ParseError, Parser};
+
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Params = ();
+
+        const PARTS: u8 = 1;
+        const EXAMPLES: &'static [&'static str] = &["1\n2"];
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            let parser: Parser = Parser::create(input);
+            return parser.lines().map(|it| parser.value(it)).collect();
+        }
+
+        fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+            return Ok(Answer::from(input.iter().sum::<u32>()));
+        }
+    }
+
+    #[test]
+    fn test_solve_timed() {
+        let timed: Timed = solve_timed::<Sum>(1, None).unwrap();
+        assert_eq!(timed.answer, Ok(Answer::from(3)));
+        assert!(timed.solve.is_some());
+        assert!(solve_timed::<Sum>(2, None).is_none());
+
+        let timed: Timed = solve_timed::<Sum>(1, Some("1\nx")).unwrap();
+        assert!(timed.answer.is_err());
+        assert_eq!(timed.solve, None);
+    }
+
+    #[test]
+    fn test_record() {
+        let timed: Timed = Timed { answer: Ok(Answer::from(3)), parse: Duration::from_nanos(5), solve: Some(Duration::from_nanos(7)) };
+        assert_eq!(
+            Record::create(2023, 1, 1, Some(&timed)).to_line(),
+            r#"{"year":2023,"day":1,"part":1,"answer":"3","parse_ns":5,"solve_ns":7,"status":"solved","error":null}"#
+        );
+
+        let timed: Timed = solve_timed::<Sum>(1, Some("1\nx")).unwrap();
+        assert_eq!(Record::create(2023, 1, 1, Some(&timed)).exit_code, 65);
+
+        let record: Record = Record::create(2023, 1, 2, None);
+        assert_eq!(record.status, Status::NoExample);
+        assert_eq!(record.exit_code, 0);
+        assert!(to_document(&[record]).contains("\"status\": \"no_example\""));
+    }
+}
@@ -0,0 +1,309 @@
+//! Graph searches over implicit graphs.
+//!
+//! Graphs are never built up front: every search takes a `successors` closure returning the neighbours of a node
+//! (with the cost of the edge for the weighted searches), so only the reached part of the graph is ever explored.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Path found by a search, from one of the start nodes to the goal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+    /// Number of nodes expanded by the search
+    pub visited: usize,
+}
+
+impl<N, C> Path<N, C> {
+    pub fn start(&self) -> &N {
+        return self.nodes.first().unwrap();
+    }
+
+    pub fn end(&self) -> &N {
+        return self.nodes.last().unwrap();
+    }
+
+    /// Number of edges of the path.
+    pub fn len(&self) -> usize {
+        return self.nodes.len() - 1;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.nodes.len() <= 1;
+    }
+}
+
+/// Nodes discovered by a search with the index of the node they were reached from.
+struct Tree<N> {
+    nodes: Vec<N>,
+    parents: Vec<Option<usize>>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash> Tree<N> {
+    fn create() -> Tree<N> {
+        return Tree { nodes: Vec::new(), parents: Vec::new(), index: HashMap::new() };
+    }
+
+    fn push(&mut self, node: N, parent: Option<usize>) -> usize {
+        self.nodes.push(node.clone());
+        self.parents.push(parent);
+        self.index.insert(node, self.nodes.len() - 1);
+        return self.nodes.len() - 1;
+    }
+
+    fn path(&self, mut idx: usize) -> Vec<N> {
+        let mut path: Vec<N> = vec![self.nodes[idx].clone()];
+        while let Some(parent) = self.parents[idx] {
+            path.push(self.nodes[parent].clone());
+            idx = parent;
+        }
+        path.reverse();
+        return path;
+    }
+}
+
+/// Breadth-first search of the closest node satisfying `success`, the cost of the path is its number of edges.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut tree: Tree<N> = Tree::create();
+    let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+
+    for start in starts {
+        if !tree.index.contains_key(&start) {
+            queue.push_back((tree.push(start, None), 0));
+        }
+    }
+
+    let mut visited: usize = 0;
+    while let Some((idx, cost)) = queue.pop_front() {
+        visited += 1;
+        if success(&tree.nodes[idx]) {
+            return Some(Path { nodes: tree.path(idx), cost, visited });
+        }
+
+        for next in successors(&tree.nodes[idx]) {
+            if !tree.index.contains_key(&next) {
+                queue.push_back((tree.push(next, Some(idx)), cost + 1));
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Every node reachable from the start nodes, including them.
+pub fn reachable<N, I>(starts: impl IntoIterator<Item = N>, mut successors: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut stack: Vec<N> = Vec::new();
+    let mut seen: HashSet<N> = HashSet::new();
+
+    for start in starts {
+        if seen.insert(start.clone()) {
+            stack.push(start);
+        }
+    }
+
+    while let Some(node) = stack.pop() {
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+
+    return seen;
+}
+
+/// Cheapest path to a node satisfying `success`, the edge costs must not be negative.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    success: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    return astar(starts, successors, |_| C::default(), success);
+}
+
+/// Cheapest path to a node satisfying `success`, guided by a `heuristic` which never overestimates the remaining cost.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut tree: Tree<N> = Tree::create();
+    let mut costs: Vec<C> = Vec::new();
+    let mut closed: Vec<bool> = Vec::new();
+    let mut heap: BinaryHeap<Reverse<(C, C, usize)>> = BinaryHeap::new();
+
+    for start in starts {
+        if !tree.index.contains_key(&start) {
+            let estimate: C = heuristic(&start);
+            heap.push(Reverse((estimate, C::default(), tree.push(start, None))));
+            costs.push(C::default());
+            closed.push(false);
+        }
+    }
+
+    let mut visited: usize = 0;
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        if closed[idx] || cost > costs[idx] {
+            continue;
+        }
+        closed[idx] = true;
+        visited += 1;
+
+        let node: N = tree.nodes[idx].clone();
+        if success(&node) {
+            return Some(Path { nodes: tree.path(idx), cost, visited });
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost: C = cost + step;
+            let next_idx: usize = match tree.index.entry(next.clone()) {
+                Entry::Occupied(entry) => {
+                    let next_idx: usize = *entry.get();
+                    if closed[next_idx] || next_cost >= costs[next_idx] {
+                        continue;
+                    }
+                    costs[next_idx] = next_cost;
+                    tree.parents[next_idx] = Some(idx);
+                    next_idx
+                }
+                Entry::Vacant(_) => {
+                    costs.push(next_cost);
+                    closed.push(false);
+                    tree.push(next.clone(), Some(idx))
+                }
+            };
+            heap.push(Reverse((next_cost + heuristic(&next), next_cost, next_idx)));
+        }
+    }
+
+    return None;
+}
+
+/// Most expensive path to a node satisfying `success` which visits every node at most once.
+///
+/// The search is an exhaustive depth-first search, so it is only practical on small graphs like the compressed
+/// graphs of junctions; `max_depth` limits the number of edges of the explored paths.
+pub fn longest_path<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+    max_depth: Option<usize>,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    struct Frame<N, C> {
+        node: N,
+        cost: C,
+        successors: std::vec::IntoIter<(N, C)>,
+    }
+
+    let max_depth: usize = max_depth.unwrap_or(usize::MAX);
+    let mut best: Option<(C, Vec<N>)> = None;
+    let mut on_path: HashSet<N> = HashSet::from([start.clone()]);
+    let mut stack: Vec<Frame<N, C>> = Vec::new();
+    let mut visited: usize = 0;
+
+    let mut enter = |node: N, cost: C, depth: usize, stack: &mut Vec<Frame<N, C>>, best: &mut Option<(C, Vec<N>)>| {
+        visited += 1;
+        let is_success: bool = success(&node);
+        if is_success && best.as_ref().is_none_or(|(best_cost, _)| cost > *best_cost) {
+            let mut nodes: Vec<N> = stack.iter().map(|it| it.node.clone()).collect();
+            nodes.push(node.clone());
+            *best = Some((cost, nodes));
+        }
+        let successors: Vec<(N, C)> = if is_success || depth >= max_depth { Vec::new() } else { successors(&node).into_iter().collect() };
+        stack.push(Frame { node, cost, successors: successors.into_iter() });
+    };
+
+    enter(start, C::default(), 0, &mut stack, &mut best);
+    while let Some(frame) = stack.last_mut() {
+        match frame.successors.next() {
+            Some((next, step)) => {
+                if on_path.insert(next.clone()) {
+                    let cost: C = frame.cost + step;
+                    let depth: usize = stack.len();
+                    enter(next, cost, depth, &mut stack, &mut best);
+                }
+            }
+            None => {
+                let frame: Frame<N, C> = stack.pop().unwrap();
+                on_path.remove(&frame.node);
+            }
+        }
+    }
+
+    return best.map(|(cost, nodes)| Path { nodes, cost, visited });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Weighted graph `0 -> 1 -> 2 -> 3` with a costly shortcut `0 -> 3` and a dead end `1 -> 4`.
+    fn edges(node: &u32) -> Vec<(u32, u32)> {
+        return match node {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 2), (4, 1)],
+            2 => vec![(3, 3)],
+            _ => Vec::new(),
+        };
+    }
+
+    #[test]
+    fn test_unweighted() {
+        let path: Path<u32, usize> = bfs([0], |it| edges(it).into_iter().map(|(next, _)| next), |it| *it == 3).unwrap();
+        assert_eq!(path.nodes, vec![0, 3]);
+        assert_eq!(path.cost, 1);
+        assert_eq!(*path.end(), 3);
+
+        assert_eq!(bfs([0], |it| edges(it).into_iter().map(|(next, _)| next), |it| *it == 5), None);
+        assert_eq!(reachable([1], |it| edges(it).into_iter().map(|(next, _)| next)), HashSet::from([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_weighted() {
+        let path: Path<u32, u32> = dijkstra([0], edges, |it| *it == 3).unwrap();
+        assert_eq!(path.nodes, vec![0, 1, 2, 3]);
+        assert_eq!(path.cost, 6);
+
+        let guided: Path<u32, u32> = astar([0], edges, |it| 3u32.saturating_sub(*it), |it| *it == 3).unwrap();
+        assert_eq!((guided.nodes, guided.cost), (path.nodes, path.cost));
+        assert!(guided.visited <= path.visited);
+
+        let longest: Path<u32, u32> = longest_path(0, edges, |it| *it == 3, None).unwrap();
+        assert_eq!((longest.nodes, longest.cost), (vec![0, 3], 10));
+        assert_eq!(longest_path(0, |it| edges(it).into_iter().map(|(next, _)| (next, 1u32)), |it| *it == 3, None).unwrap().cost, 3);
+        assert_eq!(longest_path(0, |it| edges(it).into_iter().map(|(next, _)| (next, 1u32)), |it| *it == 3, Some(2)).unwrap().cost, 1);
+    }
+}
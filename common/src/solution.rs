@@ -0,0 +1,128 @@
+use crate::answer::Answer;
+use crate::error::SolveError;
+use crate::input;
+use crate::parse::ParseError;
+
+/// Solution of a single puzzle day, split into a parsing stage and a solving stage per part.
+///
+/// Parameters which are not part of the puzzle input (e.g. limits or number of steps) are passed
+/// separately, so that examples can be solved with different values than the real puzzle.
+pub trait Solution {
+    /// Parsed puzzle input, shared by both parts.
+    type Input;
+    /// Puzzle parameters, defaults are the values of the real puzzle.
+    type Params: Default;
+
+    /// Number of parts of the puzzle, the last day of the calendar has only one.
+    const PARTS: u8 = 2;
+
+    /// Examples from the puzzle description, one per part (also used by tests).
+    const EXAMPLES: &'static [&'static str];
+
+    /// Parses the puzzle input, errors point at the line and column which failed.
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<Answer, SolveError>;
+
+    fn part2(_input: &Self::Input, _params: &Self::Params) -> Result<Answer, SolveError> {
+        return Err(SolveError::InvalidParameter(format!("puzzle has only {} part(s)", Self::PARTS)));
+    }
+
+    /// Parses the raw input after normalising it.
+    fn parse_input(input: &str) -> Result<Self::Input, SolveError> {
+        return Ok(Self::parse(&input::normalize(input))?);
+    }
+
+    /// Parameters the examples of the puzzle description are solved with.
+    fn example_params() -> Self::Params {
+        return Self::Params::default();
+    }
+
+    /// Parses the raw input and solves the first part with the default parameters.
+    fn solve_part1(input: &str) -> Result<Answer, SolveError> {
+        return Self::part1(&Self::parse_input(input)?, &Self::Params::default());
+    }
+
+    /// Parses the raw input and solves the second part with the default parameters.
+    fn solve_part2(input: &str) -> Result<Answer, SolveError> {
+        return Self::part2(&Self::parse_input(input)?, &Self::Params::default());
+    }
+
+    /// Parses the raw input and solves the given part with the default parameters.
+    fn solve(part: u8, input: &str) -> Result<Answer, SolveError> {
+        return match part {
+            1 => Self::solve_part1(input),
+            2 => Self::solve_part2(input),
+            _ => Err(invalid_part(part)),
+        };
+    }
+
+    /// Solves the given part for its example, `None` when the puzzle has no example for it.
+    fn solve_example(part: u8) -> Option<Result<Answer, SolveError>> {
+        let example: &str = Self::EXAMPLES.get((part as usize).checked_sub(1)?)?;
+        return Some(Self::solve_with_example_params(part, example));
+    }
+
+    /// Parses the raw input and solves the given part with the parameters of the examples.
+    fn solve_with_example_params(part: u8, input: &str) -> Result<Answer, SolveError> {
+        let input: Self::Input = Self::parse_input(input)?;
+        return Self::solve_parsed(part, &input, &Self::example_params());
+    }
+
+    /// Solves the given part of the parsed input.
+    fn solve_parsed(part: u8, input: &Self::Input, params: &Self::Params) -> Result<Answer, SolveError> {
+        return match part {
+            1 => Self::part1(input, params),
+            2 => Self::part2(input, params),
+            _ => Err(invalid_part(part)),
+        };
+    }
+}
+
+fn invalid_part(part: u8) -> SolveError {
+    return SolveError::InvalidParameter(format!("puzzle has no part {}", part));
+}
+
+/// Checks that every example is solved the same when saved with CRLF line endings, a trailing newline or a byte
+/// order mark, used by the tests of the days.
+pub fn assert_input_variants<S: Solution>() {
+    for (idx, example) in S::EXAMPLES.iter().enumerate().take(S::PARTS as usize) {
+        let part: u8 = idx as u8 + 1;
+        let expected: Result<Answer, SolveError> = S::solve_with_example_params(part, example);
+        for variant in input::variants(example) {
+            assert_eq!(S::solve_with_example_params(part, &variant), expected, "part {} of {:?}", part, variant);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Single;
+
+    impl Solution for Single {
+        type Input = u32;
+        type Params = ();
+
+        const PARTS: u8 = 1;
+        const EXAMPLES: &'static [&'static str] = &["7"];
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            return input.trim().parse().map_err(|_| ParseError::at(input, input, "expected a number"));
+        }
+
+        fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+            return Ok(Answer::from(*input));
+        }
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(Single::solve(1, "7").unwrap(), "7");
+        assert!(matches!(Single::solve(2, "7"), Err(SolveError::InvalidParameter(_))));
+        assert!(matches!(Single::solve(0, "7"), Err(SolveError::InvalidParameter(_))));
+        assert!(Single::solve_example(0).is_none());
+        assert!(Single::solve_example(2).is_none());
+    }
+}
@@ -1,9 +1,7 @@
-use std::fs;
-use clap::Parser;
-use {{crate_name}}::{Cli, solve_part1};
+use std::process::ExitCode;
 
-fn main() {
-    let args: Cli = Cli::parse();
-    let input: String = fs::read_to_string(args.data_dir.join("input.txt")).unwrap();
-    println!("{}", solve_part1(&input));
+use {{crate_name}}::Day{{day}};
+
+fn main() -> ExitCode {
+    return common::cli::run::<Day{{day}}>({{year}}, {{day_number}}, 1);
 }
\ No newline at end of file
@@ -1,38 +1,51 @@
-use std::path::PathBuf;
-use clap::Parser;
-
-#[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
-pub struct Cli {
-    #[clap(short, long, value_parser, default_value = "./")]
-    pub data_dir: PathBuf,
-}
+use common::error::SolveError;
+use common::parse::{ParseError, Parser};
+use common::{Answer, Solution};
 
-pub fn solve_part1(input: &str) -> String {
-    return String::new();
-}
+const INPUT: &str = "";
+
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+    type Input = Vec<String>;
+    type Params = ();
 
-pub fn solve_part2(input: &str) -> String {
-    return String::new();
+    const EXAMPLES: &'static [&'static str] = &[INPUT, INPUT];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let parser: Parser = Parser::create(input);
+        return Ok(parser.lines().map(String::from).collect());
+    }
+
+    fn part1(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        return Ok(Answer::from(""));
+    }
+
+    fn part2(input: &Self::Input, _params: &()) -> Result<Answer, SolveError> {
+        return Ok(Answer::from(""));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "";
-
     #[test]
     fn test_part1() {
         todo!("haven't built test yet");
-        let result = solve_part1(INPUT);
+        let result = Day{{day}}::solve_part1(INPUT).unwrap();
         assert_eq!(result, "");
     }
 
     #[test]
     fn test_part2() {
         todo!("haven't built test yet");
-        let result = solve_part2(INPUT);
+        let result = Day{{day}}::solve_part2(INPUT).unwrap();
         assert_eq!(result, "");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_variants() {
+        common::solution::assert_input_variants::<Day{{day}}>();
+    }
+}